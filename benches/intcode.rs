@@ -0,0 +1,79 @@
+//! Compares the interpreter with and without predecoded instructions.
+//!
+//! Run with `cargo bench --bench intcode`.
+
+use std::time::{Duration, Instant};
+
+use aoc::intcode::{assemble, Machine, QueueProvider};
+
+// Sums 1..=N for the input N, one iteration at a time.
+const TRIANGLE: &str = "
+          IN [n]
+    loop: ADD [sum], [n], [sum]
+          ADD [n], #-1, [n]
+          JNZ [n], #loop
+          OUT [sum]
+          HLT
+    n:    DATA 0
+    sum:  DATA 0
+";
+
+// Patches its own loop bound before running, like the noun/verb programs of day 2.
+const SELF_MODIFYING: &str = "
+          IN [bound]
+          ADD [bound], #0, [loop + 6]
+    loop: ADD [i], #1, [i]
+          LT [i], #0, [t]
+          JNZ [t], #loop
+          OUT [i]
+          HLT
+    i:    DATA 0
+    t:    DATA 0
+    bound: DATA 0
+";
+
+fn run(program: &[i64], input: i64, predecode: bool) -> i64 {
+    let mut machine = Machine::with_provider(program.to_vec(), QueueProvider::with_inputs([input]));
+    machine.predecode = predecode;
+    machine.run().unwrap();
+
+    machine.last_output().unwrap()
+}
+
+fn time<F: FnMut() -> i64>(mut f: F) -> (i64, Duration) {
+    let start = Instant::now();
+    let result = f();
+
+    (result, start.elapsed())
+}
+
+fn compare<F: Fn(bool) -> i64>(name: &str, workload: F) {
+    let (baseline, slow) = time(|| workload(false));
+    let (predecoded, fast) = time(|| workload(true));
+    assert_eq!(baseline, predecoded, "{}: results differ", name);
+
+    println!(
+        "{:<24} decode every step: {:>9.2?}  predecoded: {:>9.2?}  speedup: {:.2}x",
+        name,
+        slow,
+        fast,
+        slow.as_secs_f64() / fast.as_secs_f64()
+    );
+}
+
+fn main() {
+    let triangle = assemble(TRIANGLE).unwrap();
+    let self_modifying = assemble(SELF_MODIFYING).unwrap();
+
+    compare("one long run", |predecode| {
+        run(&triangle, 2_000_000, predecode)
+    });
+    compare("10,000 short runs", |predecode| {
+        (0..10_000)
+            .map(|input| run(&triangle, input % 100 + 1, predecode))
+            .sum()
+    });
+    compare("self-modifying loop", |predecode| {
+        run(&self_modifying, 1_000_000, predecode)
+    });
+}
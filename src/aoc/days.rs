@@ -0,0 +1,78 @@
+//! The solutions, one module per day, and the registry the runner picks them from.
+//!
+//! Day 25 is played by hand through the `d25p1` binary, so it has no entry here.
+
+pub mod d01;
+pub mod d02;
+pub mod d04;
+pub mod d05;
+pub mod d06;
+pub mod d07;
+pub mod d09;
+pub mod d11;
+pub mod d13;
+pub mod d16;
+pub mod d17;
+pub mod d19;
+pub mod d21;
+pub mod d23;
+
+use std::io::Read;
+
+use super::solution::{Puzzle, Solution};
+use super::utils::BufferedInput;
+
+static PUZZLES: [Puzzle; 27] = [
+    Puzzle::new(1, 1, "The Tyranny of the Rocket Equation", d01::part_one),
+    Puzzle::new(1, 2, "The Tyranny of the Rocket Equation", d01::part_two),
+    Puzzle::new(2, 1, "1202 Program Alarm", d02::part_one),
+    Puzzle::new(2, 2, "1202 Program Alarm", d02::part_two),
+    Puzzle::new(4, 1, "Secure Container", d04::part_one),
+    Puzzle::new(4, 2, "Secure Container", d04::part_two),
+    Puzzle::new(5, 1, "Sunny with a Chance of Asteroids", d05::part_one),
+    Puzzle::new(5, 2, "Sunny with a Chance of Asteroids", d05::part_two),
+    Puzzle::new(6, 1, "Universal Orbit Map", d06::part_one),
+    Puzzle::new(6, 2, "Universal Orbit Map", d06::part_two),
+    Puzzle::new(7, 1, "Amplification Circuit", d07::part_one),
+    Puzzle::new(7, 2, "Amplification Circuit", d07::part_two),
+    Puzzle::new(9, 1, "Sensor Boost", d09::part_one),
+    Puzzle::new(9, 2, "Sensor Boost", d09::part_two),
+    Puzzle::new(11, 1, "Space Police", d11::part_one),
+    Puzzle::new(11, 2, "Space Police", d11::part_two),
+    Puzzle::new(13, 1, "Care Package", d13::part_one),
+    Puzzle::new(13, 2, "Care Package", d13::part_two),
+    Puzzle::new(16, 1, "Flawed Frequency Transmission", d16::part_one),
+    Puzzle::new(16, 2, "Flawed Frequency Transmission", d16::part_two),
+    Puzzle::new(17, 1, "Set and Forget", d17::part_one),
+    Puzzle::new(17, 2, "Set and Forget", d17::part_two),
+    Puzzle::new(19, 1, "Tractor Beam", d19::part_one),
+    Puzzle::new(21, 1, "Springdroid Adventure", d21::part_one),
+    Puzzle::new(21, 2, "Springdroid Adventure", d21::part_two),
+    Puzzle::new(23, 1, "Category Six", d23::part_one),
+    Puzzle::new(23, 2, "Category Six", d23::part_two),
+];
+
+/// Every solution, ordered by day and part.
+pub fn solutions() -> impl Iterator<Item = &'static dyn Solution> {
+    PUZZLES.iter().map(|puzzle| puzzle as &dyn Solution)
+}
+
+pub fn find(day: u32, part: u32) -> Option<&'static dyn Solution> {
+    solutions().find(|solution| solution.day() == day && solution.part() == part)
+}
+
+/// Entry point of the `dNNpM` binaries: reads the input from the file given
+/// on the command line or from STDIN and prints the answer.
+pub fn run_standalone(day: u32, part: u32) -> std::io::Result<()> {
+    let solution = find(day, part).ok_or_else(|| {
+        std::io::Error::other(format!("No solution for day {} part {}", day, part))
+    })?;
+
+    let mut input = BufferedInput::parse_args(&solution.description())?;
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+
+    println!("{}", solution.solve(&text)?);
+
+    Ok(())
+}
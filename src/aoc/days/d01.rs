@@ -0,0 +1,38 @@
+use itertools::iterate;
+
+use crate::solution::Answer;
+use crate::utils::invalid_input;
+
+fn parse_input(input: &str) -> std::io::Result<Vec<i32>> {
+    input
+        .lines()
+        .map(|line| {
+            line.parse()
+                .map_err(|_| invalid_input(format!("Invalid module weight `{}`", line)))
+        })
+        .collect()
+}
+
+fn fuel_req(weight: &i32) -> i32 {
+    weight / 3 - 2
+}
+
+fn fuel_req_chained(weight: i32) -> i32 {
+    let chain = iterate(fuel_req(&weight), fuel_req);
+
+    chain.take_while(|&req| req > 0).sum()
+}
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    let module_weights = parse_input(input)?;
+    let summed_req: i32 = module_weights.iter().map(fuel_req).sum();
+
+    Ok(summed_req.into())
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    let module_weights = parse_input(input)?;
+    let summed_req: i32 = module_weights.into_iter().map(fuel_req_chained).sum();
+
+    Ok(summed_req.into())
+}
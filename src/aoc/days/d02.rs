@@ -0,0 +1,37 @@
+use itertools::Itertools;
+
+use crate::intcode::{Machine, ValueProvider};
+use crate::solution::Answer;
+use crate::utils::read_intcode_program;
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+
+    let io = &mut ValueProvider::new(0);
+    let mut machine = Machine::new(program, io);
+    machine.write(1, 12)?;
+    machine.write(2, 2)?;
+
+    machine.run()?;
+
+    Ok(machine.read(0).into())
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+    let io = &mut ValueProvider::new(0);
+
+    for (noun, verb) in Itertools::cartesian_product(0..100, 0..100) {
+        let mut machine = Machine::new(program.clone(), io);
+        machine.write(1, noun)?;
+        machine.write(2, verb)?;
+
+        machine.run()?;
+
+        if machine.read(0) == 19690720 {
+            return Ok((100 * noun + verb).into());
+        }
+    }
+
+    Err(std::io::Error::other("No noun and verb produce 19690720"))
+}
@@ -0,0 +1,84 @@
+use std::io::Error;
+
+use itertools::Itertools;
+
+use crate::solution::Answer;
+use crate::utils::invalid_input;
+
+fn parse_input(input: &str) -> std::io::Result<(i32, i32)> {
+    let line = input
+        .lines()
+        .next()
+        .ok_or_else(|| Error::other("Input has no content"))?;
+
+    let bounds: Vec<i32> = line
+        .split('-')
+        .map(|n| {
+            n.parse()
+                .map_err(|_| invalid_input(format!("Invalid password bound `{}`", n)))
+        })
+        .try_collect()?;
+
+    bounds
+        .into_iter()
+        .collect_tuple()
+        .ok_or_else(|| invalid_input(format!("Expected bounds as `LOW-HIGH`, got `{}`", line)))
+}
+
+fn is_valid(password: &str) -> bool {
+    let mut is_adjacent_same = false;
+
+    for (a, b) in password.chars().tuple_windows() {
+        if a > b {
+            return false;
+        }
+
+        if a == b {
+            is_adjacent_same = true;
+        }
+    }
+
+    is_adjacent_same
+}
+
+fn is_valid_strict(password: &str) -> bool {
+    let mut group_len = 1;
+    let mut is_adjacent_pair_same = false;
+
+    for (a, b) in password.chars().tuple_windows() {
+        if a > b {
+            return false;
+        }
+
+        if a == b {
+            group_len += 1;
+        } else {
+            if group_len == 2 {
+                is_adjacent_pair_same = true;
+            }
+
+            group_len = 1;
+        }
+    }
+
+    group_len == 2 || is_adjacent_pair_same
+}
+
+fn count_valid(input: &str, is_valid: fn(&str) -> bool) -> std::io::Result<Answer> {
+    let (lower, upper) = parse_input(input)?;
+
+    let result = (lower..=upper)
+        .map(|n| n.to_string())
+        .filter(|pass| is_valid(pass.as_str()))
+        .count();
+
+    Ok(result.into())
+}
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    count_valid(input, is_valid)
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    count_valid(input, is_valid_strict)
+}
@@ -0,0 +1,26 @@
+use crate::intcode::{Machine, ValueProvider};
+use crate::solution::Answer;
+use crate::utils::read_intcode_program;
+
+/// Runs the diagnostic program for the system with the given ID.
+fn diagnostic_code(input: &str, system_id: i64) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+
+    let io = &mut ValueProvider::new(system_id);
+    let mut machine = Machine::new(program, io);
+    machine.run()?;
+
+    let code = machine
+        .last_output()
+        .ok_or_else(|| std::io::Error::other("Program produced no output"))?;
+
+    Ok(code.into())
+}
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    diagnostic_code(input, 1)
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    diagnostic_code(input, 5)
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use itertools::{process_results, EitherOrBoth, Itertools};
+
+use crate::solution::Answer;
+use crate::utils::invalid_input;
+
+type OrbitMap = HashMap<String, Vec<String>>;
+
+fn parse_input(input: &str) -> std::io::Result<OrbitMap> {
+    let orbits = input.lines().map(|line| {
+        line.split(')')
+            .map_into()
+            .collect_tuple()
+            .ok_or_else(|| invalid_input(format!("Expected `A)B`, got `{}`", line)))
+    });
+
+    process_results(orbits, |orbits| orbits.into_group_map())
+}
+
+fn calculate_orbit_checksum(orbits: OrbitMap) -> i32 {
+    let mut searchspace = vec![("COM", 0)];
+    let mut checksum = 0;
+
+    while let Some((center, length)) = searchspace.pop() {
+        if let Some(neighbors) = orbits.get(center) {
+            let new_searches = neighbors.iter().map(|body| (body.as_str(), length + 1));
+            searchspace.extend(new_searches);
+        }
+
+        checksum += length;
+    }
+
+    checksum
+}
+
+fn find_path_to<'a>(orbits: &'a OrbitMap, target: &str) -> Option<Vec<&'a str>> {
+    let mut searchspace = vec![("COM", vec![])];
+
+    while let Some((center, path)) = searchspace.pop() {
+        if center == target {
+            return Some(path);
+        }
+
+        if let Some(neighbors) = orbits.get(center) {
+            let new_searches = neighbors.iter().map(|body| {
+                let mut new_path = path.clone();
+                new_path.push(center);
+                (body.as_str(), new_path)
+            });
+            searchspace.extend(new_searches);
+        }
+    }
+
+    None
+}
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    let orbits = parse_input(input)?;
+
+    Ok(calculate_orbit_checksum(orbits).into())
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    let orbits = parse_input(input)?;
+
+    let self_path = find_path_to(&orbits, "YOU")
+        .ok_or_else(|| invalid_input("YOU is not in orbit around COM"))?;
+    let santa_path = find_path_to(&orbits, "SAN")
+        .ok_or_else(|| invalid_input("SAN is not in orbit around COM"))?;
+
+    let zipped = self_path.into_iter().zip_longest(santa_path);
+
+    let split_paths = zipped.skip_while(|either| {
+        let both = either.as_ref().both();
+        itertools::any(both, |(a, b)| a == b)
+    });
+
+    let transfers: i32 = split_paths
+        .map(|either| match either {
+            EitherOrBoth::Both(_, _) => 2,
+            _ => 1,
+        })
+        .sum();
+
+    Ok(transfers.into())
+}
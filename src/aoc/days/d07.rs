@@ -0,0 +1,33 @@
+use itertools::{process_results, Itertools};
+
+use crate::intcode::Pipeline;
+use crate::solution::Answer;
+use crate::utils::read_intcode_program;
+
+/// Highest thruster signal over every ordering of the `phases`.
+fn max_signal(
+    input: &str,
+    phases: std::ops::Range<i64>,
+    feedback: bool,
+) -> std::io::Result<Answer> {
+    let program = &read_intcode_program(input.as_bytes())?;
+
+    let runs = phases.permutations(5).map(|phases| {
+        let mut amplifiers = Pipeline::new(program, &phases);
+        amplifiers.feedback = feedback;
+
+        amplifiers.run(0)
+    });
+    let result = process_results(runs, |outputs| outputs.flatten().max())?
+        .ok_or_else(|| std::io::Error::other("Amplifiers produced no signal"))?;
+
+    Ok(result.into())
+}
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    max_signal(input, 0..5, false)
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    max_signal(input, 5..10, true)
+}
@@ -0,0 +1,27 @@
+use crate::intcode::{Machine, ValueProvider};
+use crate::solution::Answer;
+use crate::utils::read_intcode_program;
+
+fn boost(input: &str, mode: i64) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+
+    let io = &mut ValueProvider::new(mode);
+    let mut machine = Machine::new(program, io);
+    machine.run()?;
+
+    let code = machine
+        .last_output()
+        .ok_or_else(|| std::io::Error::other("Program produced no output"))?;
+
+    Ok(code.into())
+}
+
+/// Runs BOOST in test mode.
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    boost(input, 1)
+}
+
+/// Runs BOOST in sensor boost mode.
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    boost(input, 2)
+}
@@ -0,0 +1,271 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::grid::{Bounds, Direction, Point, SparseGrid};
+use crate::image::{Image, ImageFormat, Rgb};
+use crate::intcode::{IoProvider, Machine};
+use crate::ocr;
+use crate::solution::Answer;
+use crate::utils::{invalid_input, read_intcode_program};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    White,
+}
+
+enum InstructionState {
+    AwaitingColor,
+    AwaitingTurn,
+}
+
+impl Color {
+    pub fn from_value(val: i64) -> Option<Self> {
+        match val {
+            0 => Some(Color::Black),
+            1 => Some(Color::White),
+            _ => None,
+        }
+    }
+
+    pub fn value(&self) -> i64 {
+        match self {
+            Color::Black => 0,
+            Color::White => 1,
+        }
+    }
+}
+
+impl InstructionState {
+    pub fn advance(&mut self) {
+        *self = match self {
+            InstructionState::AwaitingColor => InstructionState::AwaitingTurn,
+            InstructionState::AwaitingTurn => InstructionState::AwaitingColor,
+        }
+    }
+}
+
+pub struct PainterBot {
+    start: Color,
+    grid: SparseGrid<Color>,
+    /// Every panel painted, in order.
+    history: Vec<(Point, Color)>,
+    position: Point,
+    facing: Direction,
+    state: InstructionState,
+    /// The first instruction the robot could not follow, after which it ignores the rest.
+    error: Option<String>,
+}
+
+/// How the hull is drawn in images.
+pub struct Palette {
+    /// Side length of a panel, in pixels.
+    pub scale: usize,
+    pub black: Rgb,
+    pub white: Rgb,
+    pub robot: Rgb,
+}
+
+/// Images to save of the painted hull.
+pub struct ExportOptions {
+    /// Where to save the identifier; the extension picks the format.
+    pub image: Option<PathBuf>,
+    /// Directory to save the robot's progress to, as `frame_NNNNN.png`.
+    pub frames: Option<PathBuf>,
+    /// Panels painted between consecutive frames.
+    pub frame_step: usize,
+    pub palette: Palette,
+}
+
+impl PainterBot {
+    /// A robot standing on a panel of the `start` color, with the rest of the hull black.
+    pub fn new(start: Color) -> Self {
+        PainterBot {
+            start,
+            grid: [(Point::ORIGIN, start)].into_iter().collect(),
+            history: Vec::new(),
+            position: Point::ORIGIN,
+            facing: Direction::North,
+            state: InstructionState::AwaitingColor,
+            error: None,
+        }
+    }
+
+    pub fn history(&self) -> &[(Point, Color)] {
+        &self.history
+    }
+
+    /// Number of panels painted at least once.
+    pub fn n_painted(&self) -> usize {
+        let painted: HashSet<Point> = self.history.iter().map(|&(position, _)| position).collect();
+
+        painted.len()
+    }
+
+    pub fn white_panels(&self) -> SparseGrid<()> {
+        self.grid
+            .iter()
+            .filter(|(_, &color)| color == Color::White)
+            .map(|(position, _)| (position, ()))
+            .collect()
+    }
+
+    /// Draws the hull after each of the first `n_painted` panels was painted,
+    /// with the robot where it moved next.
+    pub fn frame(&self, n_painted: usize, bounds: Bounds, palette: &Palette) -> Image {
+        let mut hull: SparseGrid<Color> = [(Point::ORIGIN, self.start)].into_iter().collect();
+        for &(position, color) in self.history[..n_painted].iter() {
+            hull.insert(position, color);
+        }
+        let robot = self
+            .history
+            .get(n_painted)
+            .map_or(self.position, |&(position, _)| position);
+
+        Image::from_bounds(bounds, palette.scale, |point| match hull.get(point) {
+            _ if point == robot => palette.robot,
+            Some(&color) => palette.color(color),
+            None => palette.black,
+        })
+    }
+
+    /// Bounds of every panel the robot went over.
+    pub fn path_bounds(&self) -> Bounds {
+        let visited = self.history.iter().map(|&(position, _)| position);
+
+        Bounds::of(visited.chain([Point::ORIGIN, self.position])).unwrap()
+    }
+
+    fn current_color(&self) -> Color {
+        self.grid
+            .get(self.position)
+            .copied()
+            .unwrap_or(Color::Black)
+    }
+
+    fn read_instruction(&mut self, instr: i64) {
+        if self.error.is_some() {
+            return;
+        }
+
+        match self.state {
+            InstructionState::AwaitingColor => {
+                let color = match Color::from_value(instr) {
+                    Some(color) => color,
+                    None => {
+                        self.error = Some(format!("Invalid color {} from the program", instr));
+                        return;
+                    }
+                };
+
+                self.grid.insert(self.position, color);
+                self.history.push((self.position, color));
+            }
+            InstructionState::AwaitingTurn => {
+                self.facing = match instr {
+                    0 => self.facing.turn_left(),
+                    1 => self.facing.turn_right(),
+                    _ => {
+                        self.error = Some(format!("Invalid turn {} from the program", instr));
+                        return;
+                    }
+                };
+
+                self.position = self.position.step(self.facing);
+            }
+        };
+
+        self.state.advance();
+    }
+}
+
+impl Palette {
+    pub fn color(&self, color: Color) -> Rgb {
+        match color {
+            Color::Black => self.black,
+            Color::White => self.white,
+        }
+    }
+}
+
+impl IoProvider for PainterBot {
+    fn send_input(&mut self) -> i64 {
+        self.current_color().value()
+    }
+
+    fn get_output(&mut self, value: i64) {
+        self.read_instruction(value);
+    }
+}
+
+/// Runs the painting program with the robot starting on a `start` panel.
+pub fn paint(program: Vec<i64>, start: Color) -> std::io::Result<PainterBot> {
+    let mut bot = PainterBot::new(start);
+    Machine::new(program, &mut bot).run()?;
+
+    match bot.error.take() {
+        Some(error) => Err(invalid_input(error)),
+        None => Ok(bot),
+    }
+}
+
+/// The letters drawn by the white panels, or the panels themselves
+/// when they are not made of known letters, for a human to read.
+pub fn read_identifier(white_panels: &SparseGrid<()>) -> String {
+    match ocr::recognize(white_panels.points()) {
+        Ok(letters) => letters,
+        Err(_) => white_panels.render(|panel| match panel {
+            Some(_) => '#',
+            None => ' ',
+        }),
+    }
+}
+
+/// Paints the hull starting on a white panel, saves the images asked for in `options`
+/// and returns the identifier.
+pub fn paint_and_export(program: Vec<i64>, options: &ExportOptions) -> std::io::Result<String> {
+    let bot = paint(program, Color::White)?;
+    let white_panels = bot.white_panels();
+    let palette = &options.palette;
+
+    if let (Some(path), Some(bounds)) = (&options.image, white_panels.bounds()) {
+        Image::from_bounds(bounds, palette.scale, |point| {
+            match white_panels.get(point) {
+                Some(_) => palette.white,
+                None => palette.black,
+            }
+        })
+        .save(path)?;
+    }
+
+    if let Some(dir) = &options.frames {
+        std::fs::create_dir_all(dir)?;
+
+        let bounds = bot.path_bounds();
+        let n_painted = bot.history().len();
+        let counts = (0..n_painted)
+            .step_by(options.frame_step)
+            .chain([n_painted]);
+
+        for (n_frame, count) in (1..).zip(counts) {
+            let name = format!("frame_{:05}.{}", n_frame, ImageFormat::Png.extension());
+            bot.frame(count, bounds, palette).save(dir.join(name))?;
+        }
+    }
+
+    Ok(read_identifier(&white_panels))
+}
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+    let bot = paint(program, Color::Black)?;
+
+    Ok(bot.n_painted().into())
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+    let bot = paint(program, Color::White)?;
+
+    Ok(read_identifier(&bot.white_panels()).into())
+}
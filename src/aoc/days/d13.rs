@@ -0,0 +1,251 @@
+use std::cmp::Ordering;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::Duration;
+
+use crate::grid::{Point, SparseGrid};
+use crate::intcode::{Machine, QueueProvider, RunState};
+use crate::solution::Answer;
+use crate::utils::read_intcode_program;
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+const RESET_COLOR: &str = "\x1b[0m";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+/// Where joystick moves come from.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Control {
+    #[default]
+    Auto,
+    /// One move per line of STDIN: `a` or `h` for left, `d` or `l` for right, anything else stays put.
+    Keyboard,
+}
+
+/// How a game is shown while it is played.
+#[derive(Default)]
+pub struct PlayOptions {
+    pub control: Control,
+    /// Draws every frame to STDOUT, in color.
+    pub draw: bool,
+    /// Pause after drawing each frame.
+    pub delay: Duration,
+    /// Directory to write every frame to as text, named `frame_NNNNN.txt`.
+    pub dump_frames: Option<PathBuf>,
+}
+
+pub struct Arcade {
+    screen: SparseGrid<Tile>,
+    pending: Vec<i64>,
+    paddle_x: i64,
+    ball_x: i64,
+    score: i64,
+}
+
+impl Tile {
+    /// The tile drawn for `id`, or `None` if the game doesn't know it.
+    pub fn from_id(id: i64) -> Option<Self> {
+        match id {
+            0 => Some(Tile::Empty),
+            1 => Some(Tile::Wall),
+            2 => Some(Tile::Block),
+            3 => Some(Tile::Paddle),
+            4 => Some(Tile::Ball),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '#',
+            Tile::Block => '*',
+            Tile::Paddle => '-',
+            Tile::Ball => 'o',
+        }
+    }
+
+    /// ANSI escape code setting the foreground color the tile is drawn in.
+    pub fn color(&self) -> &'static str {
+        match self {
+            Tile::Empty | Tile::Wall => "\x1b[37m",
+            Tile::Block => "\x1b[33m",
+            Tile::Paddle => "\x1b[36m",
+            Tile::Ball => "\x1b[31m",
+        }
+    }
+}
+
+impl Arcade {
+    pub fn new() -> Self {
+        Arcade {
+            screen: SparseGrid::new(),
+            pending: Vec::new(),
+            paddle_x: 0,
+            ball_x: 0,
+            score: 0,
+        }
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn n_blocks(&self) -> usize {
+        self.screen
+            .iter()
+            .filter(|(_, &tile)| tile == Tile::Block)
+            .count()
+    }
+
+    /// Applies the `x, y, id` triples output since the last frame, ignoring unknown tile ids.
+    pub fn update<I: IntoIterator<Item = i64>>(&mut self, outputs: I) {
+        self.pending.extend(outputs);
+
+        for triple in std::mem::take(&mut self.pending).chunks(3) {
+            match *triple {
+                [-1, 0, score] => self.score = score,
+                [x, y, id] => {
+                    let tile = match Tile::from_id(id) {
+                        Some(tile) => tile,
+                        None => continue,
+                    };
+
+                    match tile {
+                        Tile::Paddle => self.paddle_x = x,
+                        Tile::Ball => self.ball_x = x,
+                        _ => (),
+                    }
+                    self.screen.insert(Point::new(x, y), tile);
+                }
+                _ => self.pending.extend_from_slice(triple),
+            }
+        }
+    }
+
+    /// Keeps the paddle under the ball.
+    pub fn auto_joystick(&self) -> i64 {
+        match Ord::cmp(&self.paddle_x, &self.ball_x) {
+            Ordering::Greater => -1,
+            Ordering::Less => 1,
+            Ordering::Equal => 0,
+        }
+    }
+
+    /// The score line followed by the screen, as plain text.
+    pub fn frame(&self) -> String {
+        let screen = self.screen.render(|tile| tile.map_or(' ', Tile::symbol));
+
+        format!("Score: {}\n{}\n", self.score, screen)
+    }
+
+    /// The frame with every tile colored, to be drawn over the previous one.
+    pub fn ansi_frame(&self) -> String {
+        let mut result = format!("{}Score: {}\n", CURSOR_HOME, self.score);
+        let bounds = match self.screen.bounds() {
+            Some(bounds) => bounds,
+            None => return result,
+        };
+
+        for y in bounds.min.y..=bounds.max.y {
+            for x in bounds.min.x..=bounds.max.x {
+                let tile = self.screen.get(Point::new(x, y)).unwrap_or(&Tile::Empty);
+                result.push_str(tile.color());
+                result.push(tile.symbol());
+            }
+            result.push_str(RESET_COLOR);
+            result.push('\n');
+        }
+
+        result
+    }
+}
+
+impl Default for Arcade {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn keyboard_joystick(line: &str) -> i64 {
+    match line.trim() {
+        "a" | "h" => -1,
+        "d" | "l" => 1,
+        _ => 0,
+    }
+}
+
+/// Plays the game for free until the program halts and returns the final score.
+pub fn play(program: Vec<i64>, options: &PlayOptions) -> std::io::Result<i64> {
+    if let Some(dir) = &options.dump_frames {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut keys = match options.control {
+        Control::Keyboard => Some(std::io::stdin().lock().lines()),
+        Control::Auto => None,
+    };
+
+    let mut cpu = Machine::with_provider(program, QueueProvider::new());
+    cpu.write(0, 2)?;
+    let mut arcade = Arcade::new();
+    let mut stdout = std::io::stdout().lock();
+    if options.draw {
+        write!(stdout, "{}", CLEAR_SCREEN)?;
+    }
+
+    for n_frame in 1.. {
+        let state = cpu.run()?;
+        arcade.update(cpu.provider_mut().drain_outputs());
+
+        if let Some(dir) = &options.dump_frames {
+            std::fs::write(
+                dir.join(format!("frame_{:05}.txt", n_frame)),
+                arcade.frame(),
+            )?;
+        }
+        if options.draw {
+            write!(stdout, "{}", arcade.ansi_frame())?;
+            stdout.flush()?;
+            sleep(options.delay);
+        }
+
+        if state == RunState::Halted {
+            break;
+        }
+        let joystick = match &mut keys {
+            Some(lines) => keyboard_joystick(&lines.next().transpose()?.unwrap_or_default()),
+            None => arcade.auto_joystick(),
+        };
+        cpu.provider_mut().push_input(joystick);
+    }
+
+    Ok(arcade.score())
+}
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+
+    let mut cpu = Machine::with_provider(program, QueueProvider::new());
+    cpu.run()?;
+
+    let mut arcade = Arcade::new();
+    arcade.update(cpu.provider_mut().drain_outputs());
+
+    Ok(arcade.n_blocks().into())
+}
+
+/// Plays the game for free, keeping the paddle under the ball until the program halts.
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+
+    Ok(play(program, &PlayOptions::default())?.into())
+}
@@ -0,0 +1,102 @@
+use std::io::Error;
+
+use itertools::iterate;
+
+use crate::solution::Answer;
+use crate::utils::invalid_input;
+
+fn parse_input(input: &str) -> std::io::Result<(&str, Vec<i32>)> {
+    let line = input
+        .lines()
+        .next()
+        .ok_or_else(|| Error::other("Input has no content"))?;
+
+    let result = line
+        .chars()
+        .map(|c| {
+            c.to_digit(10)
+                .map(|digit| digit as i32)
+                .ok_or_else(|| invalid_input(format!("Invalid signal digit `{}`", c)))
+        })
+        .collect::<std::io::Result<_>>()?;
+
+    Ok((line, result))
+}
+
+fn get_pattern(position: usize) -> impl Iterator<Item = i32> {
+    let base = [0, 1, 0, -1];
+
+    base.into_iter()
+        .flat_map(move |v| itertools::repeat_n(v, position))
+        .cycle()
+        .skip(1)
+}
+
+fn compute_phase(data: &[i32]) -> Vec<i32> {
+    (1..data.len() + 1)
+        .map(|pos| {
+            let pattern = get_pattern(pos);
+
+            let summed: i32 = data.iter().zip(pattern).map(|(v, pat)| v * pat).sum();
+
+            summed.abs() % 10
+        })
+        .collect()
+}
+
+/// A phase over the second half of a signal, where every pattern is zeros followed by ones.
+fn compute_phase_tail(data: &[i32]) -> Vec<i32> {
+    let mut transformed: Vec<i32> = data
+        .iter()
+        .rev()
+        .scan(0, |sum, val| {
+            *sum += val;
+
+            Some(*sum % 10)
+        })
+        .collect();
+
+    transformed.reverse();
+
+    transformed
+}
+
+fn run_phases(initial: Vec<i32>, n: usize, phase: fn(&[i32]) -> Vec<i32>) -> Vec<i32> {
+    iterate(initial, |d| phase(d)).nth(n).unwrap()
+}
+
+fn message(digits: &[i32]) -> std::io::Result<Answer> {
+    let code: String = digits
+        .get(..8)
+        .ok_or_else(|| invalid_input("Signal is shorter than the 8 digit message"))?
+        .iter()
+        .map(|digit| std::char::from_digit(*digit as u32, 10).unwrap())
+        .collect();
+
+    Ok(code.into())
+}
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    let (_, received_data) = parse_input(input)?;
+    let computed = run_phases(received_data, 100, compute_phase);
+
+    message(&computed)
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    let (line, received_data) = parse_input(input)?;
+    let message_offset: usize = line
+        .get(..7)
+        .ok_or_else(|| invalid_input("Signal is shorter than the 7 digit message offset"))?
+        .parse()
+        .map_err(invalid_input)?;
+
+    let real_data: Vec<i32> = itertools::repeat_n(received_data, 10000)
+        .flatten()
+        .skip(message_offset)
+        .collect();
+
+    let computed = run_phases(real_data, 100, compute_phase_tail);
+
+    message(&computed)
+}
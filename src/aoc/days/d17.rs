@@ -0,0 +1,147 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::grid::{Direction, SparseGrid};
+use crate::intcode::AsciiConsole;
+use crate::solution::Answer;
+use crate::utils::{invalid_input, read_intcode_program};
+
+const MAX_ROUTINE_LEN: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Move {
+    turn: char,
+    steps: usize,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.turn, self.steps)
+    }
+}
+
+fn routine(moves: &[Move]) -> String {
+    moves.iter().join(",")
+}
+
+fn trace_path(view: &str) -> std::io::Result<Vec<Move>> {
+    let scaffold = SparseGrid::parse(view, |c| match c {
+        '#' => Some(None),
+        _ => Direction::from_arrow(c).map(Some),
+    });
+
+    let (mut position, mut facing) = scaffold
+        .iter()
+        .find_map(|(p, robot)| robot.map(|facing| (p, facing)))
+        .ok_or_else(|| invalid_input(format!("No robot on the camera view:\n{}", view)))?;
+    let mut moves = Vec::new();
+
+    loop {
+        let (turn, new_facing) = if scaffold.contains(position.step(facing.turn_left())) {
+            ('L', facing.turn_left())
+        } else if scaffold.contains(position.step(facing.turn_right())) {
+            ('R', facing.turn_right())
+        } else {
+            break;
+        };
+        facing = new_facing;
+
+        let mut steps = 0;
+        while scaffold.contains(position.step(facing)) {
+            position = position.step(facing);
+            steps += 1;
+        }
+
+        moves.push(Move { turn, steps });
+    }
+
+    Ok(moves)
+}
+
+fn compress<'a>(rest: &'a [Move], functions: &mut Vec<&'a [Move]>, main: &mut Vec<usize>) -> bool {
+    if rest.is_empty() {
+        return true;
+    }
+    if main.len() * 2 >= MAX_ROUTINE_LEN {
+        return false;
+    }
+
+    for index in 0..functions.len() {
+        let function = functions[index];
+
+        if rest.starts_with(function) {
+            main.push(index);
+            if compress(&rest[function.len()..], functions, main) {
+                return true;
+            }
+            main.pop();
+        }
+    }
+
+    if functions.len() < 3 {
+        for len in 1..=rest.len() {
+            let function = &rest[..len];
+            if routine(function).len() > MAX_ROUTINE_LEN {
+                break;
+            }
+
+            functions.push(function);
+            main.push(functions.len() - 1);
+            if compress(&rest[len..], functions, main) {
+                return true;
+            }
+            main.pop();
+            functions.pop();
+        }
+    }
+
+    false
+}
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+
+    let view = AsciiConsole::new(program).read_until_prompt()?.text;
+    let scaffold = SparseGrid::parse(&view, |c| "#^v<>".contains(c).then_some(()));
+
+    let result: i64 = scaffold
+        .points()
+        .filter(|p| p.neighbours().all(|n| scaffold.contains(n)))
+        .map(|p| p.x * p.y)
+        .sum();
+
+    Ok(result.into())
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    let mut program = read_intcode_program(input.as_bytes())?;
+
+    let view = AsciiConsole::new(program.clone()).read_until_prompt()?.text;
+    let path = trace_path(&view)?;
+
+    let mut functions = Vec::new();
+    let mut main = Vec::new();
+    if !compress(&path, &mut functions, &mut main) {
+        return Err(std::io::Error::other(
+            "Path cannot be split into 3 functions",
+        ));
+    }
+
+    let main_routine = main.iter().map(|&i| (b'A' + i as u8) as char).join(",");
+    let mut script = vec![main_routine];
+    script.extend(functions.iter().map(|function| routine(function)));
+    script.resize(4, String::new());
+    script.push("n".to_string());
+
+    program[0] = 2;
+    let mut robot = AsciiConsole::new(program);
+    let output = robot.run_script(script.iter().map(String::as_str))?;
+
+    let result = output
+        .values
+        .last()
+        .ok_or_else(|| std::io::Error::other(output.text.clone()))?;
+
+    Ok((*result).into())
+}
@@ -0,0 +1,23 @@
+use itertools::iproduct;
+
+use crate::intcode::{Machine, QueueProvider};
+use crate::solution::Answer;
+use crate::utils::{invalid_input, read_intcode_program};
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+
+    let result: i64 = iproduct!(0..50, 0..50)
+        .map(|(x, y)| {
+            let checker = &mut QueueProvider::with_inputs([x, y]);
+            let mut cpu = Machine::new(program.clone(), checker);
+
+            cpu.run()?;
+            checker
+                .pop_output()
+                .ok_or_else(|| invalid_input("Drone program gave no reading"))
+        })
+        .sum::<std::io::Result<_>>()?;
+
+    Ok(result.into())
+}
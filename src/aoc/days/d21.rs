@@ -0,0 +1,37 @@
+use crate::intcode::AsciiConsole;
+use crate::solution::Answer;
+use crate::utils::read_intcode_program;
+
+// Jump if there is a hole in the next three tiles and ground to land on.
+const WALK_SCRIPT: &[&str] = &[
+    "NOT A J", "NOT B T", "OR T J", "NOT C T", "OR T J", "AND D J", "WALK",
+];
+
+// As when walking, but only jump if after landing the droid can step forward or jump again.
+const RUN_SCRIPT: &[&str] = &[
+    "NOT A J", "NOT B T", "OR T J", "NOT C T", "OR T J", "AND D J", "NOT E T", "NOT T T", "OR H T",
+    "AND T J", "RUN",
+];
+
+fn survey_hull(input: &str, springscript: &[&str]) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+
+    let mut droid = AsciiConsole::new(program);
+    let output = droid.run_script(springscript.iter().copied())?;
+
+    // Without a damage report, the droid fell and the output shows how.
+    let result = output
+        .values
+        .last()
+        .ok_or_else(|| std::io::Error::other(output.text.clone()))?;
+
+    Ok((*result).into())
+}
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    survey_hull(input, WALK_SCRIPT)
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    survey_hull(input, RUN_SCRIPT)
+}
@@ -0,0 +1,29 @@
+use crate::intcode::{HookAction, Nat, Network, Packet};
+use crate::solution::Answer;
+use crate::utils::read_intcode_program;
+
+pub fn part_one(input: &str) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+
+    let mut network = Network::new(&program, 50);
+    network.add_hook(255, |packet: Packet| HookAction::Stop(packet.y));
+
+    let result = network
+        .run()?
+        .ok_or_else(|| std::io::Error::other("No packet was sent to address 255"))?;
+
+    Ok(result.into())
+}
+
+pub fn part_two(input: &str) -> std::io::Result<Answer> {
+    let program = read_intcode_program(input.as_bytes())?;
+
+    let mut network = Network::new(&program, 50);
+    network.add_hook(255, Nat::new());
+
+    let result = network
+        .run()?
+        .ok_or_else(|| std::io::Error::other("Network went idle with no NAT packet"))?;
+
+    Ok(result.into())
+}
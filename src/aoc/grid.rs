@@ -0,0 +1,478 @@
+//! Points, directions and grids for the puzzles that play out on a 2D plane.
+//!
+//! `y` grows downwards, matching maps read line by line from the input or a camera,
+//! so `North` is `(0, -1)` and rendering starts from the smallest `y`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Sub};
+
+use itertools::Itertools;
+
+use super::utils::invalid_input;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// The smallest rectangle containing a set of points, with both corners inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+/// Cells stored only where set, for grids whose extent is not known up front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+/// A rectangle of cells with its top-left corner at the origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Returned when the lines of a dense grid are not all the same length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaggedLineError {
+    pub line: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+const EIGHT_NEIGHBOURS: [(i64, i64); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.delta()
+    }
+
+    /// The four orthogonally adjacent points, clockwise from north.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// All eight adjacent points including diagonals, clockwise from north.
+    pub fn neighbours_diagonal(self) -> impl Iterator<Item = Point> {
+        EIGHT_NEIGHBOURS
+            .into_iter()
+            .map(move |(dx, dy)| self + Point::new(dx, dy))
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Direction {
+    /// Clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    /// Reads the arrows used to draw robots, `^`, `>`, `v` and `<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+impl Bounds {
+    /// Returns `None` when there are no points.
+    pub fn of<I: IntoIterator<Item = Point>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Bounds::new(first, first), |bounds, p| bounds.including(p)))
+    }
+
+    pub fn new(min: Point, max: Point) -> Self {
+        Bounds { min, max }
+    }
+
+    /// The smallest bounds containing both these bounds and `point`.
+    pub fn including(self, point: Point) -> Self {
+        Bounds {
+            min: Point::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Every point inside, row by row from the top.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = (self.min, self.max);
+
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+
+    /// Draws every point inside, one line per row from the top.
+    pub fn render<F: FnMut(Point) -> char>(&self, mut cell: F) -> String {
+        (self.min.y..=self.max.y)
+            .map(|y| {
+                (self.min.x..=self.max.x)
+                    .map(|x| cell(Point::new(x, y)))
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid::default()
+    }
+
+    /// Reads a map with one character per cell, keeping the cells `cell` returns a value for.
+    /// The first character of the first line is at the origin.
+    pub fn parse<F: FnMut(char) -> Option<T>>(text: &str, mut cell: F) -> Self {
+        let mut grid = SparseGrid::new();
+
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(value) = cell(c) {
+                    grid.insert(Point::new(x as i64, y as i64), value);
+                }
+            }
+        }
+
+        grid
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    /// Sets a cell, returning its previous value.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    /// Number of cells set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Set cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Bounds of the set cells, or `None` when no cell is set.
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::of(self.points())
+    }
+
+    /// Draws the cells within `bounds()`, passing `None` for the ones not set.
+    pub fn render<F: FnMut(Option<&T>) -> char>(&self, mut cell: F) -> String {
+        match self.bounds() {
+            Some(bounds) => bounds.render(|point| cell(self.get(point))),
+            None => String::new(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(cells: I) -> Self {
+        SparseGrid {
+            cells: cells.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: Clone> DenseGrid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        DenseGrid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> DenseGrid<T> {
+    /// Reads a map with one character per cell; every line must have the same length.
+    pub fn parse<F: FnMut(char) -> T>(text: &str, mut cell: F) -> Result<Self, RaggedLineError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (y, line) in text.lines().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+
+            let found = cells.len() - len;
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(RaggedLineError {
+                        line: y + 1,
+                        expected,
+                        found,
+                    })
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+
+        Ok(DenseGrid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        match self.cells.is_empty() {
+            true => None,
+            false => Some(Bounds::new(
+                Point::ORIGIN,
+                Point::new(self.width as i64 - 1, self.height as i64 - 1),
+            )),
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.index(point).is_some()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index(point).map(move |i| &mut self.cells[i])
+    }
+
+    /// Sets a cell, returning `false` if `point` is outside the grid.
+    pub fn set(&mut self, point: Point, value: T) -> bool {
+        match self.get_mut(point) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Every cell, row by row from the top.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+
+        self.cells.iter().enumerate().map(move |(i, value)| {
+            let point = Point::new((i % width) as i64, (i / width) as i64);
+            (point, value)
+        })
+    }
+
+    /// The orthogonal neighbours of `point` that are inside the grid.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbours().filter(move |&p| self.contains(p))
+    }
+
+    /// The neighbours of `point`, diagonals included, that are inside the grid.
+    pub fn neighbours_diagonal(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point
+            .neighbours_diagonal()
+            .filter(move |&p| self.contains(p))
+    }
+
+    pub fn render<F: FnMut(&T) -> char>(&self, mut cell: F) -> String {
+        match self.bounds() {
+            Some(bounds) => bounds.render(|point| cell(&self.cells[self.index(point).unwrap()])),
+            None => String::new(),
+        }
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(point.y).ok().filter(|&y| y < self.height)?;
+
+        Some(y * self.width + x)
+    }
+}
+
+impl fmt::Display for RaggedLineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected {} cells, found {}",
+            self.line, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for RaggedLineError {}
+
+impl From<RaggedLineError> for std::io::Error {
+    fn from(err: RaggedLineError) -> Self {
+        invalid_input(err)
+    }
+}
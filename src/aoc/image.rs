@@ -0,0 +1,274 @@
+//! Raster images drawn from grids, with encoders for PPM, PBM and PNG.
+//!
+//! The PNG encoder stores the pixel data uncompressed, which keeps it small
+//! and dependency-free; the puzzle pictures are tiny anyway.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Error, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use super::grid::{Bounds, Point};
+use super::utils::invalid_input;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+const MAX_STORED_BLOCK: usize = 0xffff;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Binary (`P6`) portable pixmap.
+    Ppm,
+    /// Binary (`P4`) portable bitmap; dark pixels become black and the rest white.
+    Pbm,
+    Png,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError(String);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    fn is_dark(&self) -> bool {
+        let Rgb(r, g, b) = *self;
+
+        u32::from(r) * 299 + u32::from(g) * 587 + u32::from(b) * 114 < 128 * 1000
+    }
+}
+
+/// Parses `#rrggbb` or `rrggbb`.
+impl FromStr for Rgb {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|c| u8::from_str_radix(c, 16).ok())
+        };
+
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Rgb(r, g, b)),
+            _ => Err(ParseColorError(s.to_string())),
+        }
+    }
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    /// Draws every point of `bounds` as a `scale` by `scale` square, the top-left point first.
+    pub fn from_bounds<F: FnMut(Point) -> Rgb>(bounds: Bounds, scale: usize, mut cell: F) -> Self {
+        let mut image = Image::new(bounds.width() * scale, bounds.height() * scale, Rgb::BLACK);
+
+        for point in bounds.points() {
+            let color = cell(point);
+            let left = (point.x - bounds.min.x) as usize * scale;
+            let top = (point.y - bounds.min.y) as usize * scale;
+
+            for y in top..top + scale {
+                image.pixels[y * image.width + left..y * image.width + left + scale].fill(color);
+            }
+        }
+
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        match x < self.width && y < self.height {
+            true => Some(self.pixels[y * self.width + x]),
+            false => None,
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        assert!(x < self.width && y < self.height, "Pixel out of bounds");
+
+        self.pixels[y * self.width + x] = color;
+    }
+
+    /// Saves the image in the format given by the extension of `path`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let format = ImageFormat::from_path(path)
+            .ok_or_else(|| Error::other(format!("Unknown image format: {}", path.display())))?;
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer, format)?;
+        writer.flush()
+    }
+
+    pub fn write<W: Write>(&self, writer: W, format: ImageFormat) -> std::io::Result<()> {
+        match format {
+            ImageFormat::Ppm => self.write_ppm(writer),
+            ImageFormat::Pbm => self.write_pbm(writer),
+            ImageFormat::Png => self.write_png(writer),
+        }
+    }
+
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+
+        let bytes: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|&Rgb(r, g, b)| [r, g, b])
+            .collect();
+        writer.write_all(&bytes)
+    }
+
+    pub fn write_pbm<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        write!(writer, "P4\n{} {}\n", self.width, self.height)?;
+
+        for row in self.rows() {
+            let bytes: Vec<u8> = row
+                .chunks(8)
+                .map(|bits| {
+                    bits.iter()
+                        .enumerate()
+                        .filter(|(_, pixel)| pixel.is_dark())
+                        .fold(0, |byte, (i, _)| byte | 0x80 >> i)
+                })
+                .collect();
+            writer.write_all(&bytes)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_png<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let mut header = Vec::new();
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, default compression, filtering and no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut scanlines = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.rows() {
+            scanlines.push(0);
+            scanlines.extend(row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        }
+
+        writer.write_all(&PNG_SIGNATURE)?;
+        write_chunk(&mut writer, b"IHDR", &header)?;
+        write_chunk(&mut writer, b"IDAT", &zlib_stored(&scanlines))?;
+        write_chunk(&mut writer, b"IEND", &[])
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Rgb]> {
+        self.pixels.chunks(self.width.max(1)).take(self.height)
+    }
+}
+
+impl ImageFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "ppm" => Some(ImageFormat::Ppm),
+            "pbm" => Some(ImageFormat::Pbm),
+            "png" => Some(ImageFormat::Png),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Pbm => "pbm",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+fn write_chunk<W: Write>(writer: &mut W, kind: &[u8; 4], data: &[u8]) -> std::io::Result<()> {
+    let crc = crc32(kind.iter().chain(data.iter()));
+
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+    writer.write_all(&crc.to_be_bytes())
+}
+
+/// Wraps `data` in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut result = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+
+    if blocks.peek().is_none() {
+        result.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+
+        result.push(u8::from(blocks.peek().is_none()));
+        result.extend(len.to_le_bytes());
+        result.extend((!len).to_le_bytes());
+        result.extend(block);
+    }
+
+    result.extend(adler32(data).to_be_bytes());
+    result
+}
+
+fn crc32<'a, I: IntoIterator<Item = &'a u8>>(bytes: I) -> u32 {
+    let crc = bytes.into_iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| match crc & 1 {
+            1 => (crc >> 1) ^ 0xedb8_8320,
+            _ => crc >> 1,
+        })
+    });
+
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+
+    let (a, b) = bytes.iter().fold((1, 0), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % MOD;
+        (a, (b + a) % MOD)
+    });
+
+    b << 16 | a
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid color `{}`, expected `#rrggbb`", self.0)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+impl From<ParseColorError> for std::io::Error {
+    fn from(err: ParseColorError) -> Self {
+        invalid_input(err)
+    }
+}
@@ -1,5 +1,39 @@
+mod analysis;
+mod asm;
+mod asynchronous;
+mod channel;
+mod console;
+mod debugger;
+mod decode;
+mod disasm;
+mod error;
 mod io;
 mod machine;
+mod memory;
+mod network;
+mod pipeline;
+mod snapshot;
+mod trace;
+mod transpile;
 
-pub use io::{IoProvider, ValueProvider};
-pub use machine::Machine;
+pub use analysis::{analyze, BasicBlock, ControlFlowGraph};
+pub use asm::{assemble, AsmError, AsmErrorKind};
+pub use asynchronous::{
+    block_on, AsyncInput, AsyncMachine, AsyncQueue, AsyncSlot, Executor, NextOutput,
+};
+pub use channel::{spawn, ChannelProvider};
+pub use console::{AsciiConsole, ConsoleOutput};
+pub use debugger::Debugger;
+pub use decode::{Arg, Args, Op};
+pub use disasm::{disassemble, encode, format_listing, Instruction};
+pub use error::IntcodeError;
+pub use io::{IoProvider, QueueProvider, ValueProvider};
+pub use machine::{Limits, Machine, RunState};
+pub use memory::{DenseMemory, Memory, PagedMemory, PAGE_SIZE};
+pub use network::{Hook, HookAction, Nat, Network, Packet};
+pub use pipeline::Pipeline;
+pub use snapshot::Snapshot;
+pub use trace::{
+    write_trace, TraceEntry, TraceFilter, TraceFormat, TraceRecorder, TraceWriter, Tracer,
+};
+pub use transpile::transpile;
@@ -0,0 +1,291 @@
+//! Static control-flow analysis of Intcode programs.
+//!
+//! Unlike `disassemble`, which decodes every word in order, the analysis only decodes
+//! what execution can reach from address 0 by following fall-through and jumps with
+//! immediate targets. Jumps through memory or the relative base are recorded as indirect,
+//! and the code they lead to is only found if something else reaches it too.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use super::decode::{Arg, Args, Op};
+use super::disasm::{decode_at, Instruction};
+
+/// A run of instructions that is only entered at its first instruction
+/// and only left after its last one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BasicBlock {
+    pub start: usize,
+    pub instructions: Vec<Instruction>,
+    /// Addresses control continues at, including negative ones
+    /// and ones that do not decode as an instruction.
+    pub successors: Vec<i64>,
+    /// Whether the block ends with a jump to an address only known at run time.
+    pub indirect: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlFlowGraph {
+    program: Vec<i64>,
+    blocks: BTreeMap<usize, BasicBlock>,
+    invalid: BTreeSet<i64>,
+}
+
+/// Where control can go after one instruction.
+struct Flow {
+    successors: Vec<i64>,
+    indirect: bool,
+    /// Whether the instruction is a jump or halt, and so has to end its block.
+    ends_block: bool,
+}
+
+/// Builds the control-flow graph of `program`, starting at address 0.
+pub fn analyze(program: &[i64]) -> ControlFlowGraph {
+    let mut instructions = BTreeMap::new();
+    let mut leaders = BTreeSet::from([0]);
+    let mut invalid = BTreeSet::new();
+    let mut pending = vec![0];
+
+    while let Some(target) = pending.pop() {
+        let address = usize::try_from(target).ok();
+        if address.is_some_and(|a| instructions.contains_key(&a)) || invalid.contains(&target) {
+            continue;
+        }
+
+        let instruction = match address.and_then(|a| decode(program, a)) {
+            Some(instruction) => instruction,
+            None => {
+                invalid.insert(target);
+                continue;
+            }
+        };
+
+        let flow = flow(&instruction);
+        if flow.ends_block {
+            let targets = flow.successors.iter().map(|&t| usize::try_from(t));
+            leaders.extend(targets.flatten());
+        }
+        pending.extend(flow.successors);
+        instructions.insert(instruction.address(), instruction);
+    }
+
+    let mut blocks = BTreeMap::new();
+    for &start in leaders.iter().filter(|a| instructions.contains_key(a)) {
+        let mut block = BasicBlock {
+            start,
+            instructions: Vec::new(),
+            successors: Vec::new(),
+            indirect: false,
+        };
+        let mut address = start;
+
+        while let Some(&instruction) = instructions.get(&address) {
+            let flow = flow(&instruction);
+            block.instructions.push(instruction);
+            address += instruction.n_words();
+
+            if flow.ends_block || leaders.contains(&address) || invalid.contains(&(address as i64))
+            {
+                block.successors = flow.successors;
+                block.indirect = flow.indirect;
+                break;
+            }
+        }
+
+        blocks.insert(start, block);
+    }
+
+    ControlFlowGraph {
+        program: program.to_vec(),
+        blocks,
+        invalid,
+    }
+}
+
+fn decode(program: &[i64], address: usize) -> Option<Instruction> {
+    if address >= program.len() {
+        return None;
+    }
+
+    match decode_at(program, address) {
+        instruction @ Instruction::Op { .. } => Some(instruction),
+        Instruction::Data { .. } => None,
+    }
+}
+
+fn flow(instruction: &Instruction) -> Flow {
+    let next = (instruction.address() + instruction.n_words()) as i64;
+
+    match *instruction {
+        Instruction::Op { op: Op::Hlt, .. } => Flow {
+            successors: vec![],
+            indirect: false,
+            ends_block: true,
+        },
+        Instruction::Op {
+            op: op @ (Op::Jnz | Op::Jez),
+            args: Args::Two(condition, target),
+            ..
+        } => {
+            // `None` when the condition is only known at run time.
+            let taken = match condition {
+                Arg::Immediate(v) => Some((v != 0) == (op == Op::Jnz)),
+                _ => None,
+            };
+
+            let mut successors = Vec::new();
+            let mut indirect = false;
+            if taken != Some(false) {
+                match target {
+                    Arg::Immediate(t) => successors.push(t),
+                    _ => indirect = true,
+                }
+            }
+            if taken != Some(true) {
+                successors.push(next);
+            }
+
+            Flow {
+                successors,
+                indirect,
+                ends_block: true,
+            }
+        }
+        _ => Flow {
+            successors: vec![next],
+            indirect: false,
+            ends_block: false,
+        },
+    }
+}
+
+/// DOT identifier of the node for `address`; negative addresses get an `n` in place of the sign.
+fn node(address: i64) -> String {
+    match address {
+        a if a < 0 => format!("bn{}", a.unsigned_abs()),
+        a => format!("b{}", a),
+    }
+}
+
+impl ControlFlowGraph {
+    /// Blocks in address order.
+    pub fn blocks(&self) -> impl Iterator<Item = &BasicBlock> {
+        self.blocks.values()
+    }
+
+    pub fn block(&self, start: usize) -> Option<&BasicBlock> {
+        self.blocks.get(&start)
+    }
+
+    /// The block containing the instruction that starts at `address`.
+    pub fn block_containing(&self, address: usize) -> Option<&BasicBlock> {
+        self.blocks
+            .range(..=address)
+            .rev()
+            .map(|(_, block)| block)
+            .find(|block| block.instructions.iter().any(|i| i.address() == address))
+    }
+
+    /// Addresses control can reach that are negative or do not hold a valid instruction.
+    pub fn invalid_targets(&self) -> impl Iterator<Item = i64> + '_ {
+        self.invalid.iter().copied()
+    }
+
+    /// Whether `address` is part of a reachable instruction.
+    pub fn is_code(&self, address: usize) -> bool {
+        self.blocks
+            .values()
+            .flat_map(|b| b.instructions.iter())
+            .any(|i| {
+                let start = i.address();
+                (start..start + i.n_words()).contains(&address)
+            })
+    }
+
+    /// The program as reachable instructions, with every other word as `Instruction::Data`,
+    /// in address order.
+    pub fn listing(&self) -> Vec<Instruction> {
+        let code: BTreeMap<usize, Instruction> = self
+            .blocks
+            .values()
+            .flat_map(|b| b.instructions.iter())
+            .map(|&i| (i.address(), i))
+            .collect();
+
+        let mut result = Vec::new();
+        let mut address = 0;
+        while address < self.program.len() {
+            let instruction = code.get(&address).copied().unwrap_or(Instruction::Data {
+                address,
+                value: self.program[address],
+            });
+
+            address += instruction.n_words();
+            result.push(instruction);
+        }
+
+        result
+    }
+
+    /// Renders the graph in Graphviz DOT format.
+    ///
+    /// Jumps are drawn solid and fall-through dashed; indirect jumps lead to a shared `?` node
+    /// and unreachable-as-code targets to red nodes.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph intcode {{").unwrap();
+        writeln!(dot, "    node [shape=box, fontname=\"monospace\"];").unwrap();
+
+        for block in self.blocks.values() {
+            let label = block
+                .instructions
+                .iter()
+                .map(|i| format!("{}: {}\\l", i.address(), i))
+                .join("");
+            writeln!(dot, "    b{} [label=\"{}\"];", block.start, label).unwrap();
+        }
+
+        for &address in self.invalid.iter() {
+            let label = match usize::try_from(address).map(|a| self.program.get(a)) {
+                Ok(Some(value)) => format!("{}: DATA {}", address, value),
+                Ok(None) => format!("{}: out of bounds", address),
+                Err(_) => format!("{}: negative address", address),
+            };
+            writeln!(
+                dot,
+                "    {} [label=\"{}\", color=red, shape=octagon];",
+                node(address),
+                label
+            )
+            .unwrap();
+        }
+
+        if self.blocks.values().any(|b| b.indirect) {
+            writeln!(dot, "    indirect [label=\"?\", shape=diamond];").unwrap();
+        }
+
+        for block in self.blocks.values() {
+            let end = block
+                .instructions
+                .last()
+                .map_or(block.start, |i| i.address() + i.n_words()) as i64;
+
+            for &successor in block.successors.iter() {
+                let style = match successor == end {
+                    true => " [style=dashed]",
+                    false => "",
+                };
+                writeln!(dot, "    b{} -> {}{};", block.start, node(successor), style).unwrap();
+            }
+
+            if block.indirect {
+                writeln!(dot, "    b{} -> indirect [style=dotted];", block.start).unwrap();
+            }
+        }
+
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+}
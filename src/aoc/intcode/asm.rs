@@ -0,0 +1,478 @@
+//! A small assembly language for Intcode.
+//!
+//! ```text
+//! ; comments start with a semicolon
+//! .alias counter rb+1          ; operand alias
+//! .macro inc dst               ; macro with parameters
+//!     ADD dst, #1, dst
+//! .endm
+//!
+//! start:  ARB #100
+//!         IN counter
+//! loop:   inc [total]
+//!         OUT [total]
+//!         JNZ counter, #loop
+//!         HLT
+//! total:  DATA 0
+//! ```
+//!
+//! Inside a macro body, `@` expands to a suffix unique to each expansion,
+//! so macros can define local labels such as `skip@`.
+//!
+//! Operands use the same sigils as the disassembler:
+//! `[expr]` for position mode, `#expr` for immediate mode and `rb+expr` for relative mode.
+//! Expressions are sums and differences of integers and labels.
+//! Lines of a disassembler listing (`12: ADD ...`) are accepted as well,
+//! with the address prefix checked against the current address.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::decode::{Arg, Args, Op};
+use super::disasm::encode;
+use crate::utils::invalid_input;
+
+const MAX_MACRO_DEPTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsmErrorKind {
+    UnknownMnemonic(String),
+    UnknownLabel(String),
+    DuplicateLabel(String),
+    InvalidOperand(String),
+    InvalidDirective(String),
+    WrongArgCount { expected: usize, found: usize },
+    ImmediateDestination,
+    AddressMismatch { expected: usize, found: usize },
+    UnterminatedMacro(String),
+    MacroRecursion(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsmError {
+    pub line: usize,
+    pub kind: AsmErrorKind,
+}
+
+#[derive(Debug, Clone)]
+struct Macro {
+    params: Vec<String>,
+    body: Vec<(usize, String)>,
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    Position(String),
+    Immediate(String),
+    Relative(String),
+}
+
+#[derive(Debug, Clone)]
+enum Statement {
+    Op(Op, Vec<Operand>),
+    Data(Vec<String>),
+}
+
+#[derive(Default)]
+struct Assembler {
+    macros: HashMap<String, Macro>,
+    aliases: HashMap<String, String>,
+    labels: HashMap<String, usize>,
+    statements: Vec<(usize, Statement)>,
+    address: usize,
+    expansions: usize,
+}
+
+/// Assembles `source` into an Intcode program.
+pub fn assemble(source: &str) -> Result<Vec<i64>, AsmError> {
+    let lines: Vec<(usize, String)> = source
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.to_string()))
+        .collect();
+
+    let mut assembler = Assembler::default();
+    assembler.process(&lines, 0)?;
+    assembler.emit()
+}
+
+impl Assembler {
+    fn process(&mut self, lines: &[(usize, String)], depth: usize) -> Result<(), AsmError> {
+        let mut lines = lines.iter();
+
+        while let Some((number, raw)) = lines.next() {
+            let number = *number;
+            let line = strip_comment(raw);
+            let error = |kind| AsmError { line: number, kind };
+
+            if let Some(rest) = line.strip_prefix(".macro") {
+                let (name, params) = split_head(rest);
+                if name.is_empty() {
+                    return Err(error(AsmErrorKind::InvalidDirective(line.to_string())));
+                }
+
+                let mut body = Vec::new();
+                loop {
+                    match lines.next() {
+                        Some((_, l)) if strip_comment(l) == ".endm" => break,
+                        Some((n, l)) => body.push((*n, l.clone())),
+                        None => return Err(error(AsmErrorKind::UnterminatedMacro(name.into()))),
+                    }
+                }
+
+                let params = split_operands(params);
+                self.macros.insert(name.to_string(), Macro { params, body });
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix(".alias") {
+                let (name, value) = split_head(rest);
+                if name.is_empty() || value.is_empty() {
+                    return Err(error(AsmErrorKind::InvalidDirective(line.to_string())));
+                }
+
+                self.aliases.insert(name.to_string(), value.to_string());
+                continue;
+            }
+
+            if line.starts_with('.') {
+                return Err(error(AsmErrorKind::InvalidDirective(line.to_string())));
+            }
+
+            let line = self.take_labels(line).map_err(error)?;
+            if line.is_empty() {
+                continue;
+            }
+
+            let (head, rest) = split_head(line);
+            let operands = split_operands(rest);
+
+            if let Some(mac) = self.macros.get(head).cloned() {
+                if depth >= MAX_MACRO_DEPTH {
+                    return Err(error(AsmErrorKind::MacroRecursion(head.to_string())));
+                }
+                if mac.params.len() != operands.len() {
+                    return Err(error(AsmErrorKind::WrongArgCount {
+                        expected: mac.params.len(),
+                        found: operands.len(),
+                    }));
+                }
+
+                self.expansions += 1;
+                let bindings: HashMap<&str, &str> = mac
+                    .params
+                    .iter()
+                    .map(String::as_str)
+                    .zip(operands.iter().map(String::as_str))
+                    .collect();
+                let suffix = format!("__{}", self.expansions);
+                let body: Vec<(usize, String)> = mac
+                    .body
+                    .iter()
+                    .map(|(_, l)| (number, substitute(l, &bindings).replace('@', &suffix)))
+                    .collect();
+
+                self.process(&body, depth + 1)?;
+                continue;
+            }
+
+            let statement = self.parse_statement(head, &operands).map_err(error)?;
+            self.address += match &statement {
+                Statement::Op(_, operands) => operands.len() + 1,
+                Statement::Data(values) => values.len(),
+            };
+            self.statements.push((number, statement));
+        }
+
+        Ok(())
+    }
+
+    fn take_labels<'a>(&mut self, mut line: &'a str) -> Result<&'a str, AsmErrorKind> {
+        while let Some((label, rest)) = line.split_once(':') {
+            let label = label.trim();
+
+            if let Ok(address) = label.parse::<usize>() {
+                if address != self.address {
+                    return Err(AsmErrorKind::AddressMismatch {
+                        expected: self.address,
+                        found: address,
+                    });
+                }
+            } else if is_identifier(label) {
+                if self
+                    .labels
+                    .insert(label.to_string(), self.address)
+                    .is_some()
+                {
+                    return Err(AsmErrorKind::DuplicateLabel(label.to_string()));
+                }
+            } else {
+                break;
+            }
+
+            line = rest.trim();
+        }
+
+        Ok(line)
+    }
+
+    fn parse_statement(&self, head: &str, operands: &[String]) -> Result<Statement, AsmErrorKind> {
+        if head.eq_ignore_ascii_case("DATA") {
+            let values = operands.iter().map(|v| self.resolve_alias(v)).collect();
+            return Ok(Statement::Data(values));
+        }
+
+        let op = Op::from_mnemonic(head)
+            .ok_or_else(|| AsmErrorKind::UnknownMnemonic(head.to_string()))?;
+
+        if op.expected_n_args() != operands.len() {
+            return Err(AsmErrorKind::WrongArgCount {
+                expected: op.expected_n_args(),
+                found: operands.len(),
+            });
+        }
+
+        let operands = operands
+            .iter()
+            .map(|o| parse_operand(&self.resolve_alias(o)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Statement::Op(op, operands))
+    }
+
+    fn resolve_alias(&self, operand: &str) -> String {
+        self.aliases
+            .get(operand)
+            .cloned()
+            .unwrap_or_else(|| operand.to_string())
+    }
+
+    fn emit(&self) -> Result<Vec<i64>, AsmError> {
+        let mut program = Vec::with_capacity(self.address);
+
+        for (number, statement) in &self.statements {
+            let error = |kind| AsmError {
+                line: *number,
+                kind,
+            };
+
+            match statement {
+                Statement::Data(values) => {
+                    for value in values {
+                        program.push(self.evaluate(value).map_err(error)?);
+                    }
+                }
+                Statement::Op(op, operands) => {
+                    let args = operands
+                        .iter()
+                        .map(|o| self.resolve_operand(o))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(error)?;
+                    let args = match args[..] {
+                        [] => Args::Zero,
+                        [a] => Args::One(a),
+                        [a, b] => Args::Two(a, b),
+                        [a, b, c] => Args::Three(a, b, c),
+                        _ => unreachable!(),
+                    };
+
+                    if let Some(Arg::Immediate(_)) = op.destination(&args) {
+                        return Err(error(AsmErrorKind::ImmediateDestination));
+                    }
+
+                    program.extend(encode(*op, &args));
+                }
+            }
+        }
+
+        Ok(program)
+    }
+
+    fn resolve_operand(&self, operand: &Operand) -> Result<Arg, AsmErrorKind> {
+        let arg = match operand {
+            Operand::Position(expr) => Arg::Position(self.evaluate(expr)?),
+            Operand::Immediate(expr) => Arg::Immediate(self.evaluate(expr)?),
+            Operand::Relative(expr) => Arg::Relative(self.evaluate(expr)?),
+        };
+
+        Ok(arg)
+    }
+
+    fn evaluate(&self, expr: &str) -> Result<i64, AsmErrorKind> {
+        let invalid = || AsmErrorKind::InvalidOperand(expr.to_string());
+        let mut total = 0;
+        let mut sign = 1;
+        let mut expect_term = true;
+        let mut rest = expr.trim();
+
+        while !rest.is_empty() {
+            if expect_term {
+                if let Some(r) = rest.strip_prefix('-') {
+                    sign = -sign;
+                    rest = r.trim_start();
+                    continue;
+                }
+
+                let end = rest
+                    .find(|c: char| c == '+' || c == '-' || c.is_whitespace())
+                    .unwrap_or(rest.len());
+                let term = &rest[..end];
+                // Negative literals are parsed with their sign, so `i64::MIN` is in range.
+                let literal = match sign {
+                    1 => term.parse::<i64>(),
+                    _ => format!("-{}", term).parse::<i64>(),
+                };
+                let value = match literal {
+                    Ok(v) => v,
+                    Err(_) if is_identifier(term) => {
+                        let label = *self
+                            .labels
+                            .get(term)
+                            .ok_or_else(|| AsmErrorKind::UnknownLabel(term.to_string()))?
+                            as i64;
+                        label.checked_mul(sign).ok_or_else(invalid)?
+                    }
+                    Err(_) => return Err(invalid()),
+                };
+
+                total = i64::checked_add(total, value).ok_or_else(invalid)?;
+                expect_term = false;
+                rest = rest[end..].trim_start();
+            } else {
+                sign = match rest.chars().next() {
+                    Some('+') => 1,
+                    Some('-') => -1,
+                    _ => return Err(invalid()),
+                };
+                expect_term = true;
+                rest = rest[1..].trim_start();
+            }
+        }
+
+        if expect_term {
+            return Err(invalid());
+        }
+
+        Ok(total)
+    }
+}
+
+fn parse_operand(text: &str) -> Result<Operand, AsmErrorKind> {
+    let invalid = || AsmErrorKind::InvalidOperand(text.to_string());
+    let text = text.trim();
+
+    if let Some(expr) = text.strip_prefix('#') {
+        return Ok(Operand::Immediate(expr.to_string()));
+    }
+
+    if let Some(expr) = text.strip_prefix('[') {
+        let expr = expr.strip_suffix(']').ok_or_else(invalid)?;
+        return Ok(Operand::Position(expr.to_string()));
+    }
+
+    if let Some(expr) = text.strip_prefix("rb") {
+        let expr = expr.trim_start();
+        return match expr.chars().next() {
+            None => Ok(Operand::Relative("0".to_string())),
+            Some('+') => Ok(Operand::Relative(expr[1..].to_string())),
+            Some('-') => Ok(Operand::Relative(expr.to_string())),
+            _ => Err(invalid()),
+        };
+    }
+
+    Err(invalid())
+}
+
+fn substitute(line: &str, bindings: &HashMap<&str, &str>) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c.is_ascii_alphabetic() || c == '_' {
+            let mut end = start + c.len_utf8();
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+
+            let word = &line[start..end];
+            result.push_str(bindings.get(word).copied().unwrap_or(word));
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+fn strip_comment(line: &str) -> &str {
+    line.split(';').next().unwrap_or("").trim()
+}
+
+fn split_head(line: &str) -> (&str, &str) {
+    let line = line.trim();
+
+    match line.split_once(char::is_whitespace) {
+        Some((head, rest)) => (head, rest.trim()),
+        None => (line, ""),
+    }
+}
+
+fn split_operands(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(|o| o.trim().to_string())
+        .filter(|o| !o.is_empty())
+        .collect()
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && text != "rb"
+}
+
+impl fmt::Display for AsmErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsmErrorKind::UnknownMnemonic(m) => write!(f, "unknown mnemonic or macro `{}`", m),
+            AsmErrorKind::UnknownLabel(l) => write!(f, "unknown label `{}`", l),
+            AsmErrorKind::DuplicateLabel(l) => write!(f, "label `{}` defined twice", l),
+            AsmErrorKind::InvalidOperand(o) => write!(f, "invalid operand `{}`", o),
+            AsmErrorKind::InvalidDirective(d) => write!(f, "invalid directive `{}`", d),
+            AsmErrorKind::WrongArgCount { expected, found } => {
+                write!(f, "expected {} operands, found {}", expected, found)
+            }
+            AsmErrorKind::ImmediateDestination => {
+                write!(f, "destination operand in immediate mode")
+            }
+            AsmErrorKind::AddressMismatch { expected, found } => {
+                write!(
+                    f,
+                    "address prefix {} does not match address {}",
+                    found, expected
+                )
+            }
+            AsmErrorKind::UnterminatedMacro(m) => write!(f, "macro `{}` lacks .endm", m),
+            AsmErrorKind::MacroRecursion(m) => write!(f, "macro `{}` nests too deeply", m),
+        }
+    }
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::error::Error for AsmError {}
+
+impl From<AsmError> for std::io::Error {
+    fn from(err: AsmError) -> Self {
+        invalid_input(err)
+    }
+}
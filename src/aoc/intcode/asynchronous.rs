@@ -0,0 +1,268 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use super::error::IntcodeError;
+use super::io::IoProvider;
+use super::machine::{Machine, RunState};
+use super::memory::{DenseMemory, Memory};
+
+/// Instructions an `AsyncMachine` may execute in one poll before yielding to other tasks.
+const BUDGET: usize = 4096;
+
+/// A source of input values that may not be ready yet.
+pub trait AsyncInput {
+    /// Returns `Ready(None)` once no more input will ever arrive.
+    fn poll_input(&mut self, cx: &mut Context<'_>) -> Poll<Option<i64>>;
+}
+
+/// The provider inside an `AsyncMachine`, holding the input it was last handed
+/// and the outputs not yet taken.
+#[derive(Debug, Default)]
+pub struct AsyncSlot {
+    input: Option<i64>,
+    outputs: VecDeque<i64>,
+}
+
+/// A machine that waits on an `AsyncInput` instead of blocking,
+/// yielding its outputs through `next_output`.
+#[derive(Debug)]
+pub struct AsyncMachine<I, M = DenseMemory> {
+    machine: Machine<AsyncSlot, M>,
+    input: I,
+}
+
+/// Future returned by `AsyncMachine::next_output`.
+#[derive(Debug)]
+pub struct NextOutput<'a, I, M> {
+    machine: &'a mut AsyncMachine<I, M>,
+}
+
+/// An unbounded queue connecting async machines. Clones share the same queue.
+#[derive(Debug, Clone, Default)]
+pub struct AsyncQueue {
+    state: Arc<Mutex<QueueState>>,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    values: VecDeque<i64>,
+    wakers: Vec<Waker>,
+    closed: bool,
+}
+
+/// A minimal single-threaded executor, enough to drive many machines at once.
+#[derive(Default)]
+pub struct Executor {
+    tasks: Vec<Option<Pin<Box<dyn Future<Output = ()>>>>>,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+struct TaskWaker {
+    index: usize,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+struct ThreadWaker(Thread);
+
+impl IoProvider for AsyncSlot {
+    fn send_input(&mut self) -> i64 {
+        self.input.take().expect("No input ready")
+    }
+
+    fn try_send_input(&mut self) -> Option<i64> {
+        self.input.take()
+    }
+
+    fn get_output(&mut self, value: i64) {
+        self.outputs.push_back(value);
+    }
+}
+
+impl<I: AsyncInput> AsyncMachine<I> {
+    pub fn new(program: Vec<i64>, input: I) -> Self {
+        AsyncMachine::from_machine(Machine::with_provider(program, AsyncSlot::default()), input)
+    }
+}
+
+impl<I: AsyncInput, M: Memory> AsyncMachine<I, M> {
+    /// Wraps a machine set up beforehand, e.g. with another memory backend or limits.
+    pub fn from_machine(machine: Machine<AsyncSlot, M>, input: I) -> Self {
+        AsyncMachine { machine, input }
+    }
+
+    pub fn machine(&self) -> &Machine<AsyncSlot, M> {
+        &self.machine
+    }
+
+    pub fn input_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Runs until the next output, returning `None` once the program halts
+    /// or its input source is exhausted.
+    pub fn next_output(&mut self) -> NextOutput<'_, I, M> {
+        NextOutput { machine: self }
+    }
+
+    /// Runs to the end, collecting every output.
+    pub async fn collect_outputs(&mut self) -> Result<Vec<i64>, IntcodeError> {
+        let mut outputs = Vec::new();
+        while let Some(value) = self.next_output().await? {
+            outputs.push(value);
+        }
+
+        Ok(outputs)
+    }
+
+    pub fn poll_output(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<i64>, IntcodeError>> {
+        for _ in 0..BUDGET {
+            if let Some(value) = self.machine.provider_mut().outputs.pop_front() {
+                return Poll::Ready(Ok(Some(value)));
+            }
+
+            match self.machine.step() {
+                Err(err) => return Poll::Ready(Err(err)),
+                Ok(RunState::Halted) => return Poll::Ready(Ok(None)),
+                Ok(RunState::NeedsInput) => match self.input.poll_input(cx) {
+                    Poll::Ready(Some(value)) => self.machine.provider_mut().input = Some(value),
+                    Poll::Ready(None) => return Poll::Ready(Ok(None)),
+                    Poll::Pending => return Poll::Pending,
+                },
+                Ok(RunState::Running | RunState::Output(_)) => (),
+            }
+        }
+
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+impl<I: AsyncInput, M: Memory> Future for NextOutput<'_, I, M> {
+    type Output = Result<Option<i64>, IntcodeError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.machine.poll_output(cx)
+    }
+}
+
+impl AsyncQueue {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn push(&self, value: i64) {
+        let mut state = self.state.lock().unwrap();
+        state.values.push_back(value);
+        state.wakers.drain(..).for_each(Waker::wake);
+    }
+
+    /// Marks the queue as finished; readers get `None` once it is drained.
+    pub fn close(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.closed = true;
+        state.wakers.drain(..).for_each(Waker::wake);
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl AsyncInput for AsyncQueue {
+    fn poll_input(&mut self, cx: &mut Context<'_>) -> Poll<Option<i64>> {
+        let mut state = self.state.lock().unwrap();
+
+        match state.values.pop_front() {
+            Some(value) => Poll::Ready(Some(value)),
+            None if state.closed => Poll::Ready(None),
+            None => {
+                state.wakers.push(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Executor {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn spawn<F: Future<Output = ()> + 'static>(&mut self, future: F) {
+        self.ready.lock().unwrap().push_back(self.tasks.len());
+        self.tasks.push(Some(Box::pin(future)));
+    }
+
+    /// Polls tasks until all of them finish or none can make progress,
+    /// returning the number of tasks left unfinished.
+    pub fn run(&mut self) -> usize {
+        loop {
+            let index = match self.ready.lock().unwrap().pop_front() {
+                Some(index) => index,
+                None => break,
+            };
+
+            let task = match self.tasks[index].as_mut() {
+                Some(task) => task,
+                None => continue,
+            };
+
+            let waker = Waker::from(Arc::new(TaskWaker {
+                index,
+                ready: Arc::clone(&self.ready),
+            }));
+            if task
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_ready()
+            {
+                self.tasks[index] = None;
+            }
+        }
+
+        self.tasks.iter().filter(|task| task.is_some()).count()
+    }
+}
+
+impl fmt::Debug for Executor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Executor")
+            .field("tasks", &self.tasks.len())
+            .finish()
+    }
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.ready.lock().unwrap().push_back(self.index);
+    }
+}
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs a future to completion on the current thread.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+use super::error::IntcodeError;
+use super::io::IoProvider;
+use super::machine::{Machine, RunState};
+use super::memory::Memory;
+
+/// Connects a machine to other threads through `mpsc` channels.
+///
+/// Reading input blocks until a value arrives. Once every sender is gone,
+/// the machine pauses with `RunState::NeedsInput` instead.
+#[derive(Debug)]
+pub struct ChannelProvider {
+    inputs: Receiver<i64>,
+    outputs: Sender<i64>,
+}
+
+impl ChannelProvider {
+    pub fn new(inputs: Receiver<i64>, outputs: Sender<i64>) -> Self {
+        ChannelProvider { inputs, outputs }
+    }
+
+    /// Creates a provider with fresh channels,
+    /// returning the ends used to feed it and to collect its outputs.
+    pub fn connected() -> (Self, Sender<i64>, Receiver<i64>) {
+        let (input_tx, input_rx) = channel();
+        let (output_tx, output_rx) = channel();
+
+        (
+            ChannelProvider::new(input_rx, output_tx),
+            input_tx,
+            output_rx,
+        )
+    }
+}
+
+impl IoProvider for ChannelProvider {
+    fn send_input(&mut self) -> i64 {
+        self.inputs.recv().expect("Input channel closed")
+    }
+
+    fn try_send_input(&mut self) -> Option<i64> {
+        self.inputs.recv().ok()
+    }
+
+    /// Outputs nobody listens for anymore are dropped.
+    fn get_output(&mut self, value: i64) {
+        self.outputs.send(value).ok();
+    }
+}
+
+/// Runs `machine` on its own thread.
+/// Joining gives the machine back along with how its run ended.
+pub fn spawn<T, M>(
+    mut machine: Machine<T, M>,
+) -> JoinHandle<(Machine<T, M>, Result<RunState, IntcodeError>)>
+where
+    T: IoProvider + Send + 'static,
+    M: Memory + 'static,
+{
+    thread::spawn(move || {
+        let result = machine.run();
+
+        (machine, result)
+    })
+}
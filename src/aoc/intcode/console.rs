@@ -0,0 +1,109 @@
+use std::io::{BufRead, Write};
+
+use super::error::IntcodeError;
+use super::io::{ascii_char, QueueProvider};
+use super::machine::Machine;
+use super::memory::{DenseMemory, Memory};
+
+/// Talks to a program that reads and writes ASCII text,
+/// e.g. the springdroid from day 21 or the text adventure from day 25.
+#[derive(Debug)]
+pub struct AsciiConsole<M = DenseMemory> {
+    machine: Machine<QueueProvider, M>,
+}
+
+/// Everything a program output until it asked for input or halted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsoleOutput {
+    pub text: String,
+    /// Outputs outside the ASCII range, usually puzzle answers.
+    pub values: Vec<i64>,
+}
+
+impl AsciiConsole {
+    pub fn new(program: Vec<i64>) -> Self {
+        AsciiConsole::from_machine(Machine::with_provider(program, QueueProvider::new()))
+    }
+}
+
+impl<M: Memory> AsciiConsole<M> {
+    pub fn from_machine(machine: Machine<QueueProvider, M>) -> Self {
+        AsciiConsole { machine }
+    }
+
+    pub fn machine(&self) -> &Machine<QueueProvider, M> {
+        &self.machine
+    }
+
+    pub fn machine_mut(&mut self) -> &mut Machine<QueueProvider, M> {
+        &mut self.machine
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.machine.is_halted()
+    }
+
+    /// Queues `line` followed by a newline.
+    pub fn send_line(&mut self, line: &str) {
+        self.machine.provider_mut().push_line(line);
+    }
+
+    /// Runs until the program waits for more input or halts.
+    pub fn read_until_prompt(&mut self) -> Result<ConsoleOutput, IntcodeError> {
+        self.machine.run()?;
+
+        let mut output = ConsoleOutput::default();
+        for value in self.machine.provider_mut().drain_outputs() {
+            match ascii_char(value) {
+                Some(c) => output.text.push(c),
+                None => output.values.push(value),
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Sends every line up front, then runs the program to its next prompt.
+    pub fn run_script<'a, I: IntoIterator<Item = &'a str>>(
+        &mut self,
+        lines: I,
+    ) -> Result<ConsoleOutput, IntcodeError> {
+        for line in lines {
+            self.send_line(line);
+        }
+
+        self.read_until_prompt()
+    }
+
+    /// Relays text between the program and a user until the program halts
+    /// or `input` runs out, returning every non-ASCII value output.
+    pub fn interact<R: BufRead, W: Write>(
+        &mut self,
+        input: R,
+        mut output: W,
+    ) -> std::io::Result<Vec<i64>> {
+        let mut values = Vec::new();
+        let mut lines = input.lines();
+
+        loop {
+            let chunk = self.read_until_prompt()?;
+            write!(output, "{}", chunk.text)?;
+            for value in chunk.values.iter() {
+                writeln!(output, "{}", value)?;
+            }
+            output.flush()?;
+            values.extend(chunk.values);
+
+            if self.is_halted() {
+                break;
+            }
+
+            match lines.next() {
+                Some(line) => self.send_line(line?.trim_end()),
+                None => break,
+            }
+        }
+
+        Ok(values)
+    }
+}
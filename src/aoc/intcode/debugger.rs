@@ -0,0 +1,365 @@
+//! A scriptable step debugger over `Machine`.
+//!
+//! Commands (one per line):
+//!
+//! - `break ADDR` / `break op MNEMONIC`: stop before an address or any instruction of a kind
+//! - `delete ADDR` / `delete op MNEMONIC`: remove a breakpoint
+//! - `watch ADDR` / `unwatch ADDR`: stop after a memory cell changes
+//! - `step [N]`: execute `N` (default 1) instructions
+//! - `continue`: run until a breakpoint, watchpoint, input wait or halt
+//! - `finish`: run until an input wait or halt, ignoring breakpoints and watchpoints
+//! - `regs`: show program counter, relative base and last output
+//! - `mem START [END]`: dump memory cells `START..=END`, at most 4096 of them
+//! - `poke ADDR VALUE`: overwrite a memory cell below the end of memory or address 2^20
+//! - `input V...` / `ascii TEXT`: queue input values or a line of text
+//! - `disas [N]`: list `N` (default 5, at most 1024) instructions from the program counter
+//! - `quit`: end the session
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use super::decode::Op;
+use super::disasm::{disassemble, Instruction};
+use super::io::QueueProvider;
+use super::machine::{Machine, RunState};
+
+const MAX_DUMP_CELLS: usize = 4096;
+const MAX_LISTING: usize = 1024;
+/// Pokes below this address may grow memory, pokes past it must land in memory already there.
+const MAX_POKE_ADDRESS: usize = 1 << 20;
+
+pub struct Debugger {
+    machine: Machine<QueueProvider>,
+    breakpoints: BTreeSet<usize>,
+    op_breakpoints: HashSet<Op>,
+    watchpoints: BTreeMap<usize, i64>,
+}
+
+enum Stop {
+    Breakpoint(usize),
+    Watchpoint(usize, i64, i64),
+    State(RunState),
+}
+
+impl Debugger {
+    pub fn new(program: Vec<i64>) -> Self {
+        Debugger {
+            machine: Machine::with_provider(program, QueueProvider::new()),
+            breakpoints: BTreeSet::new(),
+            op_breakpoints: HashSet::new(),
+            watchpoints: BTreeMap::new(),
+        }
+    }
+
+    pub fn machine(&self) -> &Machine<QueueProvider> {
+        &self.machine
+    }
+
+    /// Executes one command line and returns its report,
+    /// or `None` once the session should end.
+    pub fn execute(&mut self, line: &str) -> Option<String> {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some(command) => command,
+            None => return Some(String::new()),
+        };
+        let args: Vec<&str> = words.collect();
+
+        let result = match command {
+            "break" | "b" => self.set_breakpoint(&args, true),
+            "delete" | "d" => self.set_breakpoint(&args, false),
+            "watch" | "w" => self.set_watchpoint(&args, true),
+            "unwatch" => self.set_watchpoint(&args, false),
+            "step" | "s" => parse_optional(&args, 1).map(|n| self.step(n)),
+            "continue" | "c" => Ok(self.resume(true)),
+            "finish" | "f" => Ok(self.resume(false)),
+            "regs" | "r" => Ok(self.registers()),
+            "mem" | "x" => self.dump(&args),
+            "poke" | "p" => self.poke(&args),
+            "input" | "i" => self.queue_input(&args),
+            "ascii" => {
+                self.machine.provider_mut().push_line(&args.join(" "));
+                Ok(String::new())
+            }
+            "disas" => parse_optional(&args, 5).map(|n| self.listing(n)),
+            "quit" | "q" => return None,
+            _ => Err(format!("unknown command `{}`", command)),
+        };
+
+        Some(result.unwrap_or_else(|msg| format!("error: {}", msg)))
+    }
+
+    fn set_breakpoint(&mut self, args: &[&str], enable: bool) -> Result<String, String> {
+        match args {
+            ["op", mnemonic] => {
+                let op = Op::from_mnemonic(mnemonic)
+                    .ok_or_else(|| format!("unknown mnemonic `{}`", mnemonic))?;
+
+                if enable {
+                    self.op_breakpoints.insert(op);
+                } else {
+                    self.op_breakpoints.remove(&op);
+                }
+            }
+            [addr] => {
+                let addr = parse_address(addr)?;
+
+                if enable {
+                    self.breakpoints.insert(addr);
+                } else {
+                    self.breakpoints.remove(&addr);
+                }
+            }
+            _ => return Err("expected an address or `op MNEMONIC`".to_string()),
+        };
+
+        Ok(String::new())
+    }
+
+    fn set_watchpoint(&mut self, args: &[&str], enable: bool) -> Result<String, String> {
+        let addr = match args {
+            [addr] => parse_address(addr)?,
+            _ => return Err("expected an address".to_string()),
+        };
+
+        if enable {
+            self.watchpoints.insert(addr, self.machine.read(addr));
+        } else {
+            self.watchpoints.remove(&addr);
+        }
+
+        Ok(String::new())
+    }
+
+    fn step(&mut self, n: usize) -> String {
+        let mut report = String::new();
+
+        for _ in 0..n {
+            let stop = self.step_once();
+            let outputs = self.drain_outputs();
+            report.push_str(&outputs);
+
+            match stop {
+                Ok(Stop::State(RunState::Running | RunState::Output(_))) => continue,
+                Ok(stop) => {
+                    report.push_str(&self.describe(stop));
+                    break;
+                }
+                Err(msg) => {
+                    report.push_str(&msg);
+                    break;
+                }
+            }
+        }
+
+        report.push_str(&self.location());
+        report
+    }
+
+    fn resume(&mut self, check_breakpoints: bool) -> String {
+        let mut report = String::new();
+        let mut first = true;
+
+        loop {
+            if check_breakpoints && !first {
+                if let Some(stop) = self.breakpoint_hit() {
+                    report.push_str(&self.describe(stop));
+                    break;
+                }
+            }
+            first = false;
+
+            let stop = self.step_once();
+            report.push_str(&self.drain_outputs());
+
+            match stop {
+                Ok(Stop::State(RunState::Running | RunState::Output(_))) => {}
+                Ok(Stop::Watchpoint(..)) if !check_breakpoints => {}
+                Ok(stop) => {
+                    report.push_str(&self.describe(stop));
+                    break;
+                }
+                Err(msg) => {
+                    report.push_str(&msg);
+                    break;
+                }
+            }
+        }
+
+        report.push_str(&self.location());
+        report
+    }
+
+    fn step_once(&mut self) -> Result<Stop, String> {
+        let state = self
+            .machine
+            .step()
+            .map_err(|err| format!("fault: {}\n", err))?;
+
+        let mut changed = None;
+        for (&addr, old) in self.watchpoints.iter_mut() {
+            let new = self.machine.read(addr);
+
+            if new != *old && changed.is_none() {
+                changed = Some(Stop::Watchpoint(addr, *old, new));
+            }
+            *old = new;
+        }
+
+        Ok(changed.unwrap_or(Stop::State(state)))
+    }
+
+    fn breakpoint_hit(&self) -> Option<Stop> {
+        let pc = self.machine.program_counter();
+        let op_hit = match self.machine.next_instruction() {
+            Instruction::Op { op, .. } => self.op_breakpoints.contains(&op),
+            Instruction::Data { .. } => false,
+        };
+
+        (self.breakpoints.contains(&pc) || op_hit).then_some(Stop::Breakpoint(pc))
+    }
+
+    fn describe(&self, stop: Stop) -> String {
+        match stop {
+            Stop::Breakpoint(addr) => format!("breakpoint at {}\n", addr),
+            Stop::Watchpoint(addr, old, new) => {
+                format!("watchpoint [{}]: {} -> {}\n", addr, old, new)
+            }
+            Stop::State(RunState::Halted) => "halted\n".to_string(),
+            Stop::State(RunState::NeedsInput) => "waiting for input\n".to_string(),
+            Stop::State(_) => String::new(),
+        }
+    }
+
+    fn location(&self) -> String {
+        if self.machine.is_halted() {
+            return String::new();
+        }
+
+        format!(
+            "{}: {}",
+            self.machine.program_counter(),
+            self.machine.next_instruction()
+        )
+    }
+
+    fn registers(&self) -> String {
+        let last_output = self
+            .machine
+            .last_output()
+            .map_or_else(|| "none".to_string(), |v| v.to_string());
+
+        format!(
+            "pc={} rb={} last_output={} halted={}",
+            self.machine.program_counter(),
+            self.machine.relative_base(),
+            last_output,
+            self.machine.is_halted()
+        )
+    }
+
+    fn dump(&self, args: &[&str]) -> Result<String, String> {
+        let (start, end) = match args {
+            [start] => (parse_address(start)?, parse_address(start)?),
+            [start, end] => (parse_address(start)?, parse_address(end)?),
+            _ => return Err("expected START [END]".to_string()),
+        };
+        if end < start {
+            return Err("END is before START".to_string());
+        }
+        if end - start >= MAX_DUMP_CELLS {
+            return Err(format!(
+                "at most {} cells can be shown at once",
+                MAX_DUMP_CELLS
+            ));
+        }
+
+        let mut report = String::new();
+        for chunk in &(start..=end).chunks(8) {
+            let chunk: Vec<usize> = chunk.collect();
+            let values = chunk.iter().map(|&addr| self.machine.read(addr)).join(" ");
+
+            writeln!(report, "{}: {}", chunk[0], values).unwrap();
+        }
+
+        Ok(report.trim_end().to_string())
+    }
+
+    fn poke(&mut self, args: &[&str]) -> Result<String, String> {
+        match args {
+            [addr, value] => {
+                let addr = parse_address(addr)?;
+                let value = parse_value(value)?;
+                if addr >= self.machine.memory_len().max(MAX_POKE_ADDRESS) {
+                    return Err(format!(
+                        "address {} is too far past the end of memory",
+                        addr
+                    ));
+                }
+                self.machine
+                    .write(addr, value)
+                    .map_err(|err| err.to_string())?;
+
+                if let Some(old) = self.watchpoints.get_mut(&addr) {
+                    *old = value;
+                }
+
+                Ok(String::new())
+            }
+            _ => Err("expected ADDR VALUE".to_string()),
+        }
+    }
+
+    fn queue_input(&mut self, args: &[&str]) -> Result<String, String> {
+        let values = args
+            .iter()
+            .map(|v| parse_value(v))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.machine.provider_mut().push_inputs(values);
+
+        Ok(String::new())
+    }
+
+    fn listing(&self, n: usize) -> String {
+        let pc = self.machine.program_counter();
+        let n = n.min(MAX_LISTING);
+        let window: Vec<i64> = (pc..pc.saturating_add(n * 4))
+            .map(|addr| self.machine.read(addr))
+            .collect();
+
+        disassemble(&window)
+            .into_iter()
+            .take(n)
+            .map(|instr| format!("{}: {}", instr.address() + pc, instr))
+            .join("\n")
+    }
+
+    fn drain_outputs(&mut self) -> String {
+        self.machine
+            .provider_mut()
+            .drain_outputs()
+            .into_iter()
+            .map(|v| format!("output: {}\n", v))
+            .collect()
+    }
+}
+
+fn parse_address(text: &str) -> Result<usize, String> {
+    text.parse()
+        .map_err(|_| format!("invalid address `{}`", text))
+}
+
+fn parse_value(text: &str) -> Result<i64, String> {
+    text.parse()
+        .map_err(|_| format!("invalid value `{}`", text))
+}
+
+fn parse_optional(args: &[&str], default: usize) -> Result<usize, String> {
+    match args {
+        [] => Ok(default),
+        [n] => n.parse().map_err(|_| format!("invalid count `{}`", n)),
+        _ => Err("expected at most one count".to_string()),
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt;
+
+use itertools::{izip, Itertools};
+
+use super::error::Fault;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Op {
+    Add,
+    Mul,
+    Inp,
+    Out,
+    Jnz,
+    Jez,
+    Tlt,
+    Teq,
+    Rel,
+    Hlt,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Arg {
+    Position(i64),
+    Immediate(i64),
+    Relative(i64),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Args {
+    Zero,
+    One(Arg),
+    Two(Arg, Arg),
+    Three(Arg, Arg, Arg),
+}
+
+fn get_modes(mode_num: i64) -> impl Iterator<Item = i64> {
+    itertools::unfold(mode_num, |state| {
+        let current = *state % 10;
+        *state /= 10;
+
+        Some(current)
+    })
+}
+
+/// Decodes the instruction `word` followed by its operand words.
+pub(crate) fn decode_instruction<I: Iterator<Item = i64>>(
+    word: i64,
+    operands: I,
+) -> Result<(Op, Args), Fault> {
+    let operation = Op::from_code(word % 100)?;
+    let modes = get_modes(word / 100);
+    let mut args = izip!(modes, operands).map(Arg::from_tuple);
+
+    let result = match operation {
+        Op::Add | Op::Mul | Op::Tlt | Op::Teq => {
+            let (a, b, c) = args.next_tuple().ok_or(Fault::MalformedInstruction)?;
+            Args::Three(a?, b?, c?)
+        }
+        Op::Jnz | Op::Jez => {
+            let (a, b) = args.next_tuple().ok_or(Fault::MalformedInstruction)?;
+            Args::Two(a?, b?)
+        }
+        Op::Inp | Op::Out | Op::Rel => {
+            let (a,) = args.next_tuple().ok_or(Fault::MalformedInstruction)?;
+            Args::One(a?)
+        }
+        Op::Hlt => Args::Zero,
+    };
+
+    if let Some(Arg::Immediate(_)) = operation.destination(&result) {
+        return Err(Fault::ImmediateWrite);
+    }
+
+    Ok((operation, result))
+}
+
+impl Op {
+    pub(crate) fn from_code(code: i64) -> Result<Self, Fault> {
+        use Op::*;
+
+        let op = match code {
+            1 => Add,
+            2 => Mul,
+            3 => Inp,
+            4 => Out,
+            5 => Jnz,
+            6 => Jez,
+            7 => Tlt,
+            8 => Teq,
+            9 => Rel,
+            99 => Hlt,
+            _ => return Err(Fault::BadOpcode),
+        };
+
+        Ok(op)
+    }
+
+    pub fn code(&self) -> i64 {
+        use Op::*;
+
+        match self {
+            Add => 1,
+            Mul => 2,
+            Inp => 3,
+            Out => 4,
+            Jnz => 5,
+            Jez => 6,
+            Tlt => 7,
+            Teq => 8,
+            Rel => 9,
+            Hlt => 99,
+        }
+    }
+
+    pub fn expected_n_args(&self) -> usize {
+        use Op::*;
+
+        match self {
+            Add | Mul | Tlt | Teq => 3,
+            Jnz | Jez => 2,
+            Inp | Out | Rel => 1,
+            Hlt => 0,
+        }
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        use Op::*;
+
+        match self {
+            Add => "ADD",
+            Mul => "MUL",
+            Inp => "IN",
+            Out => "OUT",
+            Jnz => "JNZ",
+            Jez => "JEZ",
+            Tlt => "LT",
+            Teq => "EQ",
+            Rel => "ARB",
+            Hlt => "HLT",
+        }
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Self> {
+        use Op::*;
+
+        [Add, Mul, Inp, Out, Jnz, Jez, Tlt, Teq, Rel, Hlt]
+            .into_iter()
+            .find(|op| op.mnemonic().eq_ignore_ascii_case(mnemonic))
+    }
+
+    /// The argument this operation writes to, if any.
+    pub fn destination(&self, args: &Args) -> Option<Arg> {
+        match (self, args) {
+            (Op::Inp, Args::One(dest)) => Some(*dest),
+            (Op::Add | Op::Mul | Op::Tlt | Op::Teq, Args::Three(_, _, dest)) => Some(*dest),
+            _ => None,
+        }
+    }
+}
+
+impl Arg {
+    pub(crate) fn from_tuple((mode, v): (i64, i64)) -> Result<Self, Fault> {
+        match mode {
+            0 => Ok(Arg::Position(v)),
+            1 => Ok(Arg::Immediate(v)),
+            2 => Ok(Arg::Relative(v)),
+            _ => Err(Fault::BadMode(mode)),
+        }
+    }
+
+    pub fn mode(&self) -> i64 {
+        match self {
+            Arg::Position(_) => 0,
+            Arg::Immediate(_) => 1,
+            Arg::Relative(_) => 2,
+        }
+    }
+
+    pub fn value(&self) -> i64 {
+        match *self {
+            Arg::Position(v) | Arg::Immediate(v) | Arg::Relative(v) => v,
+        }
+    }
+}
+
+impl Args {
+    pub fn len(&self) -> usize {
+        match self {
+            Args::Three(_, _, _) => 3,
+            Args::Two(_, _) => 2,
+            Args::One(_) => 1,
+            _ => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Args::Zero)
+    }
+
+    pub fn to_vec(&self) -> Vec<Arg> {
+        match *self {
+            Args::Zero => vec![],
+            Args::One(a) => vec![a],
+            Args::Two(a, b) => vec![a, b],
+            Args::Three(a, b, c) => vec![a, b, c],
+        }
+    }
+
+    pub(crate) fn expect_zero(&self) -> Result<(), Fault> {
+        match self {
+            Args::Zero => Ok(()),
+            _ => Err(Fault::MalformedInstruction),
+        }
+    }
+
+    pub(crate) fn expect_one(self) -> Result<Arg, Fault> {
+        match self {
+            Args::One(arg) => Ok(arg),
+            _ => Err(Fault::MalformedInstruction),
+        }
+    }
+
+    pub(crate) fn expect_two(self) -> Result<(Arg, Arg), Fault> {
+        match self {
+            Args::Two(a, b) => Ok((a, b)),
+            _ => Err(Fault::MalformedInstruction),
+        }
+    }
+
+    pub(crate) fn expect_three(self) -> Result<(Arg, Arg, Arg), Fault> {
+        match self {
+            Args::Three(a, b, c) => Ok((a, b, c)),
+            _ => Err(Fault::MalformedInstruction),
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.mnemonic())
+    }
+}
+
+impl fmt::Display for Arg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Arg::Position(v) => write!(f, "[{}]", v),
+            Arg::Immediate(v) => write!(f, "#{}", v),
+            Arg::Relative(v) if v < 0 => write!(f, "rb{}", v),
+            Arg::Relative(v) => write!(f, "rb+{}", v),
+        }
+    }
+}
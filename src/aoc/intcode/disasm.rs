@@ -0,0 +1,110 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+use super::decode::{decode_instruction, Args, Op};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Op { address: usize, op: Op, args: Args },
+    Data { address: usize, value: i64 },
+}
+
+/// Encodes an operation with its arguments back into program words.
+pub fn encode(op: Op, args: &Args) -> Vec<i64> {
+    let args = args.to_vec();
+    let modes: i64 = args
+        .iter()
+        .rev()
+        .fold(0, |modes, arg| modes * 10 + arg.mode());
+
+    let word = modes * 100 + op.code();
+
+    std::iter::once(word)
+        .chain(args.iter().map(|arg| arg.value()))
+        .collect()
+}
+
+/// Decodes a program by linear sweep from address 0.
+///
+/// Words that do not form a complete, canonically encoded instruction become `Instruction::Data`.
+pub fn disassemble(program: &[i64]) -> Vec<Instruction> {
+    let mut result = Vec::new();
+    let mut address = 0;
+
+    while address < program.len() {
+        let instruction = decode_at(program, address);
+        address += instruction.n_words();
+
+        result.push(instruction);
+    }
+
+    result
+}
+
+/// Renders instructions as a listing with one `address: instruction` line each.
+pub fn format_listing(instructions: &[Instruction]) -> String {
+    let width = instructions
+        .last()
+        .map(|instr| instr.address().to_string().len())
+        .unwrap_or(1);
+
+    instructions
+        .iter()
+        .map(|instr| format!("{:>width$}: {}", instr.address(), instr, width = width))
+        .join("\n")
+}
+
+pub(crate) fn decode_at(program: &[i64], address: usize) -> Instruction {
+    let word = program[address];
+    let operands = program[address + 1..]
+        .iter()
+        .copied()
+        .chain(std::iter::repeat(0));
+    let data = Instruction::Data {
+        address,
+        value: word,
+    };
+
+    match decode_instruction(word, operands) {
+        Ok((op, args)) if address + args.len() < program.len() && encode(op, &args)[0] == word => {
+            Instruction::Op { address, op, args }
+        }
+        _ => data,
+    }
+}
+
+impl Instruction {
+    pub fn address(&self) -> usize {
+        match *self {
+            Instruction::Op { address, .. } | Instruction::Data { address, .. } => address,
+        }
+    }
+
+    /// Number of program words this instruction occupies.
+    pub fn n_words(&self) -> usize {
+        match self {
+            Instruction::Op { args, .. } => args.len() + 1,
+            Instruction::Data { .. } => 1,
+        }
+    }
+
+    pub fn words(&self) -> Vec<i64> {
+        match self {
+            Instruction::Op { op, args, .. } => encode(*op, args),
+            Instruction::Data { value, .. } => vec![*value],
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Op { op, args, .. } if args.is_empty() => write!(f, "{}", op),
+            Instruction::Op { op, args, .. } => {
+                write!(f, "{} {}", op, args.to_vec().iter().join(", "))
+            }
+            Instruction::Data { value, .. } => write!(f, "DATA {}", value),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntcodeError {
+    BadOpcode {
+        program_counter: usize,
+        instruction: i64,
+    },
+    BadMode {
+        program_counter: usize,
+        instruction: i64,
+        mode: i64,
+    },
+    NegativeAddress {
+        program_counter: usize,
+        instruction: i64,
+        address: i64,
+    },
+    ImmediateWrite {
+        program_counter: usize,
+        instruction: i64,
+    },
+    /// A write through `Machine::write` at an address with no cell after it.
+    AddressOutOfRange {
+        program_counter: usize,
+        instruction: i64,
+        address: usize,
+    },
+    /// An addition, multiplication or relative base adjustment left the range of `i64`.
+    ArithmeticOverflow {
+        program_counter: usize,
+        instruction: i64,
+    },
+    /// The operands following the opcode are missing or do not fit the operation.
+    MalformedInstruction {
+        program_counter: usize,
+        instruction: i64,
+    },
+    InstructionLimit {
+        program_counter: usize,
+        instruction: i64,
+        limit: u64,
+    },
+    MemoryLimit {
+        program_counter: usize,
+        instruction: i64,
+        address: usize,
+        limit: usize,
+    },
+    OutputLimit {
+        program_counter: usize,
+        instruction: i64,
+        limit: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Fault {
+    BadOpcode,
+    BadMode(i64),
+    NegativeAddress(i64),
+    ImmediateWrite,
+    AddressOutOfRange(usize),
+    ArithmeticOverflow,
+    MalformedInstruction,
+    InstructionLimit(u64),
+    MemoryLimit(usize, usize),
+    OutputLimit(u64),
+}
+
+impl Fault {
+    pub fn at(self, program_counter: usize, instruction: i64) -> IntcodeError {
+        match self {
+            Fault::BadOpcode => IntcodeError::BadOpcode {
+                program_counter,
+                instruction,
+            },
+            Fault::BadMode(mode) => IntcodeError::BadMode {
+                program_counter,
+                instruction,
+                mode,
+            },
+            Fault::NegativeAddress(address) => IntcodeError::NegativeAddress {
+                program_counter,
+                instruction,
+                address,
+            },
+            Fault::ImmediateWrite => IntcodeError::ImmediateWrite {
+                program_counter,
+                instruction,
+            },
+            Fault::AddressOutOfRange(address) => IntcodeError::AddressOutOfRange {
+                program_counter,
+                instruction,
+                address,
+            },
+            Fault::ArithmeticOverflow => IntcodeError::ArithmeticOverflow {
+                program_counter,
+                instruction,
+            },
+            Fault::MalformedInstruction => IntcodeError::MalformedInstruction {
+                program_counter,
+                instruction,
+            },
+            Fault::InstructionLimit(limit) => IntcodeError::InstructionLimit {
+                program_counter,
+                instruction,
+                limit,
+            },
+            Fault::MemoryLimit(address, limit) => IntcodeError::MemoryLimit {
+                program_counter,
+                instruction,
+                address,
+                limit,
+            },
+            Fault::OutputLimit(limit) => IntcodeError::OutputLimit {
+                program_counter,
+                instruction,
+                limit,
+            },
+        }
+    }
+}
+
+impl IntcodeError {
+    /// Whether the error comes from a `Limits` cap rather than from the program itself.
+    pub fn is_limit(&self) -> bool {
+        matches!(
+            self,
+            IntcodeError::InstructionLimit { .. }
+                | IntcodeError::MemoryLimit { .. }
+                | IntcodeError::OutputLimit { .. }
+        )
+    }
+
+    pub fn program_counter(&self) -> usize {
+        match *self {
+            IntcodeError::BadOpcode {
+                program_counter, ..
+            }
+            | IntcodeError::BadMode {
+                program_counter, ..
+            }
+            | IntcodeError::NegativeAddress {
+                program_counter, ..
+            }
+            | IntcodeError::ImmediateWrite {
+                program_counter, ..
+            }
+            | IntcodeError::AddressOutOfRange {
+                program_counter, ..
+            }
+            | IntcodeError::ArithmeticOverflow {
+                program_counter, ..
+            }
+            | IntcodeError::MalformedInstruction {
+                program_counter, ..
+            }
+            | IntcodeError::InstructionLimit {
+                program_counter, ..
+            }
+            | IntcodeError::MemoryLimit {
+                program_counter, ..
+            }
+            | IntcodeError::OutputLimit {
+                program_counter, ..
+            } => program_counter,
+        }
+    }
+
+    pub fn instruction(&self) -> i64 {
+        match *self {
+            IntcodeError::BadOpcode { instruction, .. }
+            | IntcodeError::BadMode { instruction, .. }
+            | IntcodeError::NegativeAddress { instruction, .. }
+            | IntcodeError::ImmediateWrite { instruction, .. }
+            | IntcodeError::AddressOutOfRange { instruction, .. }
+            | IntcodeError::ArithmeticOverflow { instruction, .. }
+            | IntcodeError::MalformedInstruction { instruction, .. }
+            | IntcodeError::InstructionLimit { instruction, .. }
+            | IntcodeError::MemoryLimit { instruction, .. }
+            | IntcodeError::OutputLimit { instruction, .. } => instruction,
+        }
+    }
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntcodeError::BadOpcode { .. } => write!(f, "unrecognized opcode")?,
+            IntcodeError::BadMode { mode, .. } => write!(f, "unrecognized address mode {}", mode)?,
+            IntcodeError::NegativeAddress { address, .. } => {
+                write!(f, "access at negative address {}", address)?
+            }
+            IntcodeError::ImmediateWrite { .. } => {
+                write!(f, "write access at an address in immediate mode")?
+            }
+            IntcodeError::AddressOutOfRange { address, .. } => {
+                write!(f, "address {} is out of range", address)?
+            }
+            IntcodeError::ArithmeticOverflow { .. } => write!(f, "arithmetic overflow")?,
+            IntcodeError::MalformedInstruction { .. } => {
+                write!(f, "operands do not match the operation")?
+            }
+            IntcodeError::InstructionLimit { limit, .. } => {
+                write!(f, "instruction limit of {} reached", limit)?
+            }
+            IntcodeError::MemoryLimit { address, limit, .. } => write!(
+                f,
+                "write at address {} exceeds the memory limit of {} cells",
+                address, limit
+            )?,
+            IntcodeError::OutputLimit { limit, .. } => {
+                write!(f, "output limit of {} reached", limit)?
+            }
+        };
+
+        write!(
+            f,
+            " (instruction {} at address {})",
+            self.instruction(),
+            self.program_counter()
+        )
+    }
+}
+
+impl std::error::Error for IntcodeError {}
+
+impl From<IntcodeError> for std::io::Error {
+    fn from(err: IntcodeError) -> Self {
+        std::io::Error::other(err)
+    }
+}
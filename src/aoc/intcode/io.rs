@@ -1,10 +1,32 @@
+use std::collections::VecDeque;
+
 pub trait IoProvider {
     fn send_input(&mut self) -> i64;
 
+    /// Like `send_input`, but may report that no input is available yet,
+    /// in which case the machine pauses with `RunState::NeedsInput`.
+    fn try_send_input(&mut self) -> Option<i64> {
+        Some(self.send_input())
+    }
+
     #[allow(unused_variables)]
     fn get_output(&mut self, value: i64) {}
 }
 
+impl<T: IoProvider + ?Sized> IoProvider for &mut T {
+    fn send_input(&mut self) -> i64 {
+        (**self).send_input()
+    }
+
+    fn try_send_input(&mut self) -> Option<i64> {
+        (**self).try_send_input()
+    }
+
+    fn get_output(&mut self, value: i64) {
+        (**self).get_output(value)
+    }
+}
+
 pub struct ValueProvider {
     value: i64,
 }
@@ -20,3 +42,95 @@ impl IoProvider for ValueProvider {
         self.value
     }
 }
+
+/// Buffers inputs until the machine asks for them and collects its outputs.
+///
+/// When the input queue runs dry, the machine pauses with `RunState::NeedsInput`.
+#[derive(Debug, Default, Clone)]
+pub struct QueueProvider {
+    inputs: VecDeque<i64>,
+    outputs: VecDeque<i64>,
+}
+
+impl QueueProvider {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_inputs<I: IntoIterator<Item = i64>>(inputs: I) -> Self {
+        QueueProvider {
+            inputs: inputs.into_iter().collect(),
+            outputs: VecDeque::new(),
+        }
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.inputs.push_back(value);
+    }
+
+    pub fn push_inputs<I: IntoIterator<Item = i64>>(&mut self, values: I) {
+        self.inputs.extend(values);
+    }
+
+    pub fn pending_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn pop_output(&mut self) -> Option<i64> {
+        self.outputs.pop_front()
+    }
+
+    pub fn outputs(&self) -> impl Iterator<Item = &i64> {
+        self.outputs.iter()
+    }
+
+    pub fn drain_outputs(&mut self) -> Vec<i64> {
+        self.outputs.drain(..).collect()
+    }
+
+    /// Queues the bytes of `text` as inputs.
+    pub fn push_ascii(&mut self, text: &str) {
+        self.push_inputs(text.bytes().map(i64::from));
+    }
+
+    /// Queues the bytes of `line` followed by a newline.
+    pub fn push_line(&mut self, line: &str) {
+        self.push_ascii(line);
+        self.push_input(b'\n' as i64);
+    }
+
+    /// Drains outputs as text, stopping at the first value outside the ASCII range,
+    /// which is left in the queue.
+    pub fn drain_ascii(&mut self) -> String {
+        let mut result = String::new();
+
+        while let Some(c) = self.outputs.front().copied().and_then(ascii_char) {
+            result.push(c);
+            self.outputs.pop_front();
+        }
+
+        result
+    }
+}
+
+impl IoProvider for QueueProvider {
+    fn send_input(&mut self) -> i64 {
+        self.try_send_input()
+            .expect("Error: input requested from an empty queue")
+    }
+
+    fn try_send_input(&mut self) -> Option<i64> {
+        self.inputs.pop_front()
+    }
+
+    fn get_output(&mut self, value: i64) {
+        self.outputs.push_back(value);
+    }
+}
+
+pub(crate) fn ascii_char(value: i64) -> Option<char> {
+    u8::try_from(value)
+        .ok()
+        .filter(u8::is_ascii)
+        .map(char::from)
+}
@@ -1,382 +1,541 @@
-use itertools::{izip, Itertools};
+use std::any::Any;
 use std::cmp::{PartialEq, PartialOrd};
-use std::ops::{Add as Addition, Mul as Multiply};
 
+use super::decode::{decode_instruction, Arg, Args, Op};
+use super::disasm::Instruction;
+use super::error::{Fault, IntcodeError};
 use super::io::IoProvider;
+use super::memory::{DenseMemory, Memory};
+use super::snapshot::Snapshot;
+use super::trace::{TraceEntry, Tracer};
 
 #[derive(Debug)]
-pub struct Machine<'a, T> {
-    memory: Vec<i64>,
+pub struct Machine<T, M = DenseMemory> {
+    memory: M,
     program_counter: usize,
     relative_base: i64,
     jump_flag: Option<usize>,
     halted: bool,
-    running: bool,
-    output_interrupted_flag: bool,
     last_output: Option<i64>,
-    io_provider: &'a mut T,
+    io_provider: T,
+    instructions_executed: u64,
+    outputs_produced: u64,
+    tracer: Option<Box<dyn Tracer>>,
+    pending_trace: Option<TraceEntry>,
+    decode_cache: Vec<Option<(Op, Args)>>,
     pub interrupt_on_output: bool,
+    pub limits: Limits,
+    /// Whether decoded instructions are cached by address and reused until their words are overwritten.
+    pub predecode: bool,
 }
 
-#[derive(Copy, Clone)]
-enum Op {
-    Add,
-    Mul,
-    Inp,
-    Out,
-    Jnz,
-    Jez,
-    Tlt,
-    Teq,
-    Rel,
-    Hlt,
+/// Instructions at or past this address are decoded on every step instead of cached,
+/// so a jump far into sparse memory does not grow the cache with it.
+const DECODE_CACHE_LIMIT: usize = 1 << 16;
+
+/// Caps on what a machine may do, for running untrusted programs.
+///
+/// Exceeding a cap fails the offending instruction before it has any effect,
+/// so the machine can be resumed after raising the cap.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum number of instructions executed over the machine's lifetime.
+    pub max_instructions: Option<u64>,
+    /// Maximum number of memory cells backed by storage.
+    ///
+    /// `DenseMemory` allocates every cell up to the highest address written,
+    /// so programs that write far addresses need this cap or `PagedMemory`.
+    pub max_memory: Option<usize>,
+    /// Maximum number of values output over the machine's lifetime.
+    pub max_outputs: Option<u64>,
 }
 
-#[derive(Copy, Clone)]
-enum Arg {
-    Position(i64),
-    Immediate(i64),
-    Relative(i64),
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RunState {
+    Running,
+    Halted,
+    NeedsInput,
+    Output(i64),
 }
 
-#[derive(Copy, Clone)]
-enum Args {
-    Zero,
-    One(Arg),
-    Two(Arg, Arg),
-    Three(Arg, Arg, Arg),
+fn to_address(value: i64) -> Result<usize, Fault> {
+    usize::try_from(value).map_err(|_| Fault::NegativeAddress(value))
 }
 
-fn get_modes(mode_num: i64) -> impl Iterator<Item = i64> {
-    itertools::unfold(mode_num, |state| {
-        let current = *state % 10;
-        *state /= 10;
+impl<T, M: Memory> Machine<T, M> {
+    /// Creates a machine over an already filled memory backend,
+    /// e.g. `PagedMemory::from(program)` for programs that use far-apart addresses.
+    pub fn with_memory(memory: M, io_provider: T) -> Self {
+        Machine {
+            memory,
+            program_counter: 0,
+            relative_base: 0,
+            jump_flag: None,
+            halted: false,
+            last_output: None,
+            io_provider,
+            instructions_executed: 0,
+            outputs_produced: 0,
+            tracer: None,
+            pending_trace: None,
+            decode_cache: Vec::new(),
+            interrupt_on_output: false,
+            limits: Limits::default(),
+            predecode: true,
+        }
+    }
 
-        Some(current)
-    })
-}
+    /// Reads a memory cell; cells never written read as zero.
+    pub fn read(&self, position: usize) -> i64 {
+        self.peek(position)
+    }
 
-impl Op {
-    pub fn from_code(code: i64) -> Self {
-        use Op::*;
+    /// Writes a memory cell, capped by `limits.max_memory` like the program's own writes.
+    ///
+    /// Errors carry the instruction the machine is stopped at.
+    pub fn write(&mut self, position: usize, value: i64) -> Result<(), IntcodeError> {
+        self.check_write(position)
+            .map_err(|fault| fault.at(self.program_counter, self.peek(self.program_counter)))?;
+        self.try_write_or_resize(position, value);
 
-        match code {
-            1 => Add,
-            2 => Mul,
-            3 => Inp,
-            4 => Out,
-            5 => Jnz,
-            6 => Jez,
-            7 => Tlt,
-            8 => Teq,
-            9 => Rel,
-            99 => Hlt,
-            _ => panic!("Error when parsing opcode: unrecognized opcode"),
-        }
+        Ok(())
     }
 
-    pub fn expected_n_args(&self) -> usize {
-        use Op::*;
+    pub fn memory(&self) -> &M {
+        &self.memory
+    }
 
-        match self {
-            Add | Mul | Tlt | Teq => 3,
-            Jnz | Jez => 2,
-            Inp | Out | Rel => 1,
-            Hlt => 0,
-        }
+    pub fn memory_len(&self) -> usize {
+        self.memory.len()
     }
-}
 
-impl Arg {
-    pub fn from_tuple((mode, v): (i64, i64)) -> Self {
-        match mode {
-            0 => Arg::Position(v),
-            1 => Arg::Immediate(v),
-            2 => Arg::Relative(v),
-            _ => panic!("Error when parsing mode: unrecognized address mode"),
-        }
+    pub fn program_counter(&self) -> usize {
+        self.program_counter
     }
-}
 
-impl Args {
-    pub fn len(&self) -> usize {
-        match self {
-            Args::Three(_, _, _) => 3,
-            Args::Two(_, _) => 2,
-            Args::One(_) => 1,
-            _ => 0,
-        }
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
     }
 
-    pub fn expect_zero(&self, msg: &'static str) {
-        if !matches!(self, Args::Zero) {
-            panic!("{}", msg);
+    /// Decodes the instruction at the program counter without executing it.
+    pub fn next_instruction(&self) -> Instruction {
+        let address = self.program_counter;
+        let word = self.peek(address);
+
+        match self.parse_instruction(word) {
+            Ok((op, args)) => Instruction::Op { address, op, args },
+            Err(_) => Instruction::Data {
+                address,
+                value: word,
+            },
         }
     }
 
-    pub fn expect_one(self, msg: &'static str) -> Arg {
-        match self {
-            Args::One(arg) => arg,
-            _ => panic!("{}", msg),
-        }
+    pub fn last_output(&self) -> Option<i64> {
+        self.last_output
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn instructions_executed(&self) -> u64 {
+        self.instructions_executed
+    }
+
+    pub fn outputs_produced(&self) -> u64 {
+        self.outputs_produced
+    }
+
+    pub fn provider(&self) -> &T {
+        &self.io_provider
+    }
+
+    pub fn provider_mut(&mut self) -> &mut T {
+        &mut self.io_provider
     }
 
-    pub fn expect_two(self, msg: &'static str) -> (Arg, Arg) {
-        match self {
-            Args::Two(a, b) => (a, b),
-            _ => panic!("{}", msg),
+    pub fn into_provider(self) -> T {
+        self.io_provider
+    }
+
+    /// Captures the execution state so it can be restored later, possibly in another machine.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            segments: self
+                .memory
+                .segments()
+                .into_iter()
+                .map(|(start, values)| (start, values.to_vec()))
+                .collect(),
+            program_counter: self.program_counter,
+            relative_base: self.relative_base,
+            halted: self.halted,
+            interrupt_on_output: self.interrupt_on_output,
+            last_output: self.last_output,
+            instructions_executed: self.instructions_executed,
+            outputs_produced: self.outputs_produced,
         }
     }
 
-    pub fn expect_three(self, msg: &'static str) -> (Arg, Arg, Arg) {
-        match self {
-            Args::Three(a, b, c) => (a, b, c),
-            _ => panic!("{}", msg),
+    /// Resets the execution state to `snapshot`, keeping the provider, tracer and limits.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.memory = M::from_segments(&snapshot.segments);
+        self.decode_cache.clear();
+        self.program_counter = snapshot.program_counter;
+        self.relative_base = snapshot.relative_base;
+        self.jump_flag = None;
+        self.halted = snapshot.halted;
+        self.interrupt_on_output = snapshot.interrupt_on_output;
+        self.last_output = snapshot.last_output;
+        self.instructions_executed = snapshot.instructions_executed;
+        self.outputs_produced = snapshot.outputs_produced;
+    }
+
+    /// Attaches a tracer that will see every instruction executed from now on.
+    pub fn set_tracer<R: Tracer>(&mut self, tracer: R) {
+        self.tracer = Some(Box::new(tracer));
+    }
+
+    /// Detaches the current tracer if it is of type `R`.
+    pub fn take_tracer<R: Tracer>(&mut self) -> Option<R> {
+        let is_requested_type = self
+            .tracer
+            .as_deref()
+            .is_some_and(|tracer| (tracer as &dyn Any).is::<R>());
+
+        if !is_requested_type {
+            return None;
         }
+
+        let tracer: Box<dyn Any> = self.tracer.take()?;
+        tracer.downcast().ok().map(|tracer| *tracer)
     }
-}
 
-impl<T> Machine<'_, T> {
-    pub fn read(&self, position: usize) -> i64 {
-        self.memory[position]
+    fn parse_instruction(&self, opcode_unparsed: i64) -> Result<(Op, Args), Fault> {
+        let arg_begin = self.program_counter + 1;
+        let mem_slice = (arg_begin..).map(|position| self.peek(position));
+
+        decode_instruction(opcode_unparsed, mem_slice)
     }
 
-    pub fn write(&mut self, position: usize, value: i64) {
-        self.memory[position] = value;
+    /// Drops cached instructions that the word at `position` may belong to.
+    fn invalidate_decoded(&mut self, position: usize) {
+        let end = (position + 1).min(self.decode_cache.len());
+        let start = position.saturating_sub(3).min(end);
+
+        self.decode_cache[start..end].fill(None);
     }
 
-    pub fn last_output(&self) -> Option<i64> {
-        self.last_output
+    fn decode_at_program_counter(&mut self, word: i64) -> Result<(Op, Args), Fault> {
+        let pc = self.program_counter;
+        if let Some(&Some(decoded)) = self.decode_cache.get(pc).filter(|_| self.predecode) {
+            return Ok(decoded);
+        }
+
+        let decoded = self.parse_instruction(word)?;
+        if self.predecode && pc < DECODE_CACHE_LIMIT {
+            if pc >= self.decode_cache.len() {
+                self.decode_cache.resize(pc + 1, None);
+            }
+            self.decode_cache[pc] = Some(decoded);
+        }
+
+        Ok(decoded)
     }
 
-    pub fn is_halted(&self) -> bool {
-        self.halted
+    fn peek(&self, position: usize) -> i64 {
+        self.memory.read(position)
     }
 
-    pub fn provider(&self) -> &T {
-        self.io_provider
+    fn check_write(&self, address: usize) -> Result<(), Fault> {
+        address
+            .checked_add(1)
+            .ok_or(Fault::AddressOutOfRange(address))?;
+
+        if let Some(max) = self.limits.max_memory {
+            if self.memory.allocated_after_write(address) > max {
+                return Err(Fault::MemoryLimit(address, max));
+            }
+        }
+
+        Ok(())
     }
 
-    pub fn provider_mut(&mut self) -> &mut T {
-        self.io_provider
+    fn try_write_or_resize(&mut self, position: usize, value: i64) {
+        self.memory.write(position, value);
+        self.invalidate_decoded(position);
+
+        if let Some(entry) = self.pending_trace.as_mut() {
+            entry.write = Some((position, value));
+        }
     }
 }
 
-impl<'a, T: IoProvider> Machine<'a, T> {
+impl<'a, T: IoProvider> Machine<&'a mut T> {
+    /// Creates a machine that borrows its provider for its whole lifetime.
     pub fn new(program: Vec<i64>, io_provider: &'a mut T) -> Self {
-        Machine::<'a, T> {
-            memory: program,
-            program_counter: 0,
-            relative_base: 0,
-            jump_flag: None,
-            halted: false,
-            running: false,
-            output_interrupted_flag: false,
-            last_output: None,
-            io_provider,
-            interrupt_on_output: false,
-        }
+        Machine::with_provider(program, io_provider)
+    }
+}
+
+impl<T: IoProvider> Machine<T> {
+    /// Creates a machine that owns its provider,
+    /// which can be taken back with `into_provider`.
+    pub fn with_provider(program: Vec<i64>, io_provider: T) -> Self {
+        Machine::with_memory(DenseMemory::from(program), io_provider)
+    }
+
+    /// Creates a machine resuming from `snapshot`.
+    pub fn from_snapshot(snapshot: &Snapshot, io_provider: T) -> Self {
+        let mut machine = Machine::with_provider(Vec::new(), io_provider);
+        machine.restore(snapshot);
+
+        machine
     }
+}
 
-    pub fn run(&mut self) {
-        while !self.halted {
-            self.run_until_interrupt();
+impl<T: IoProvider, M: Memory> Machine<T, M> {
+    /// Runs until the program halts or blocks on an `Inp` with no input available.
+    pub fn run(&mut self) -> Result<RunState, IntcodeError> {
+        loop {
+            match self.run_until_interrupt()? {
+                RunState::Running | RunState::Output(_) => continue,
+                state => return Ok(state),
+            }
         }
     }
 
-    pub fn step(&mut self) {
-        if !self.halted {
-            self.step_internal();
-            self.check_flags();
+    /// Executes a single instruction.
+    /// Returns `RunState::Running` unless the instruction halted, blocked on input or produced output.
+    pub fn step(&mut self) -> Result<RunState, IntcodeError> {
+        if self.halted {
+            return Ok(RunState::Halted);
         }
+
+        self.step_internal()
     }
 
-    pub fn run_until_interrupt(&mut self) {
-        self.running = true;
+    /// Runs until the program halts, blocks on input
+    /// or, if `interrupt_on_output` is set, produces an output.
+    pub fn run_until_interrupt(&mut self) -> Result<RunState, IntcodeError> {
         loop {
-            self.step_internal();
-            self.check_flags();
-
-            if !self.running {
-                break;
+            match self.step()? {
+                RunState::Running => continue,
+                RunState::Output(_) if !self.interrupt_on_output => continue,
+                state => return Ok(state),
             }
         }
     }
 
-    fn step_internal(&mut self) {
-        let (opcode, args) = self.parse_instruction();
-        self.exec(opcode, args);
+    fn step_internal(&mut self) -> Result<RunState, IntcodeError> {
+        let instruction = self.peek(self.program_counter);
+        let program_counter = self.program_counter;
 
-        self.program_counter = self
-            .jump_flag
-            .take()
-            .unwrap_or_else(|| self.program_counter + args.len() + 1);
+        self.step_decoded(instruction)
+            .map_err(|fault| fault.at(program_counter, instruction))
     }
 
-    fn check_flags(&mut self) {
-        if self.output_interrupted_flag {
-            self.running = false;
-            self.output_interrupted_flag = false;
+    fn step_decoded(&mut self, instruction: i64) -> Result<RunState, Fault> {
+        if let Some(max) = self.limits.max_instructions {
+            if self.instructions_executed >= max {
+                return Err(Fault::InstructionLimit(max));
+            }
         }
+
+        let (opcode, args) = self.decode_at_program_counter(instruction)?;
+
+        if self.tracer.is_some() {
+            let entry = TraceEntry::new(
+                self.instructions_executed,
+                self.program_counter,
+                opcode,
+                args,
+            );
+            self.pending_trace = Some(entry);
+        }
+
+        let state = self.exec(opcode, args);
+        let pending_trace = self.pending_trace.take();
+        let state = state?;
+
+        if state != RunState::NeedsInput {
+            self.program_counter = self
+                .jump_flag
+                .take()
+                .unwrap_or_else(|| self.program_counter + args.len() + 1);
+            self.instructions_executed += 1;
+
+            if let (Some(tracer), Some(entry)) = (self.tracer.as_mut(), pending_trace) {
+                tracer.trace(&entry);
+            }
+        }
+
+        Ok(state)
     }
 
-    fn exec(&mut self, opcode: Op, args: Args) {
+    fn exec(&mut self, opcode: Op, args: Args) -> Result<RunState, Fault> {
         use Op::*;
 
         match opcode {
-            Add => self.arithmetic_operation(args, Addition::add),
-            Mul => self.arithmetic_operation(args, Multiply::mul),
-            Inp => self.input_operation(args),
-            Out => self.output_operation(args),
-            Jnz => self.jump_operation(args, |v| v != 0),
-            Jez => self.jump_operation(args, |v| v == 0),
-            Tlt => self.compare_operation(args, PartialOrd::lt),
-            Teq => self.compare_operation(args, PartialEq::eq),
-            Rel => self.relative_base_operation(args),
-            Hlt => self.terminate(args),
+            Add => self.arithmetic_operation(args, i64::checked_add)?,
+            Mul => self.arithmetic_operation(args, i64::checked_mul)?,
+            Inp => return self.input_operation(args),
+            Out => return self.output_operation(args),
+            Jnz => self.jump_operation(args, |v| v != 0)?,
+            Jez => self.jump_operation(args, |v| v == 0)?,
+            Tlt => self.compare_operation(args, PartialOrd::lt)?,
+            Teq => self.compare_operation(args, PartialEq::eq)?,
+            Rel => self.relative_base_operation(args)?,
+            Hlt => return self.terminate(args),
         };
+
+        Ok(RunState::Running)
     }
 
-    fn terminate(&mut self, args: Args) {
-        args.expect_zero("Error: terminate received non-empty arguments");
+    fn terminate(&mut self, args: Args) -> Result<RunState, Fault> {
+        args.expect_zero()?;
 
-        self.running = false;
         self.halted = true;
+
+        Ok(RunState::Halted)
     }
 
-    fn arithmetic_operation<F: FnOnce(i64, i64) -> i64>(&mut self, args: Args, op: F) {
-        let (arg_a, arg_b, arg_dest) =
-            args.expect_three("Error: invalid arguments for comparison operation");
-        let a = self.get_value_from_arg(arg_a);
-        let b = self.get_value_from_arg(arg_b);
-        let dest_addr = self.get_address_from_arg(arg_dest);
+    fn arithmetic_operation<F: FnOnce(i64, i64) -> Option<i64>>(
+        &mut self,
+        args: Args,
+        op: F,
+    ) -> Result<(), Fault> {
+        let (arg_a, arg_b, arg_dest) = args.expect_three()?;
+        let a = self.get_value_from_arg(arg_a)?;
+        let b = self.get_value_from_arg(arg_b)?;
+        let dest_addr = self.get_address_from_arg(arg_dest)?;
 
-        let result = op(a, b);
+        let result = op(a, b).ok_or(Fault::ArithmeticOverflow)?;
         self.try_write_or_resize(dest_addr, result);
+
+        Ok(())
     }
 
-    fn compare_operation<F: FnOnce(&i64, &i64) -> bool>(&mut self, args: Args, op: F) {
-        let (arg_a, arg_b, arg_dest) =
-            args.expect_three("Error: invalid arguments for comparison operation");
-        let a = &self.get_value_from_arg(arg_a);
-        let b = &self.get_value_from_arg(arg_b);
-        let dest_addr = self.get_address_from_arg(arg_dest);
+    fn compare_operation<F: FnOnce(&i64, &i64) -> bool>(
+        &mut self,
+        args: Args,
+        op: F,
+    ) -> Result<(), Fault> {
+        let (arg_a, arg_b, arg_dest) = args.expect_three()?;
+        let a = &self.get_value_from_arg(arg_a)?;
+        let b = &self.get_value_from_arg(arg_b)?;
+        let dest_addr = self.get_address_from_arg(arg_dest)?;
 
         let result = op(a, b) as i64;
         self.try_write_or_resize(dest_addr, result);
+
+        Ok(())
     }
 
-    fn jump_operation<F: FnOnce(i64) -> bool>(&mut self, args: Args, op: F) {
-        let (arg_cond, arg_addr) = args.expect_two("Error: invalid arguments for jump operation");
-        let cond = self.get_value_from_arg(arg_cond);
-        let addr = self.get_value_from_arg(arg_addr) as usize;
+    fn jump_operation<F: FnOnce(i64) -> bool>(&mut self, args: Args, op: F) -> Result<(), Fault> {
+        let (arg_cond, arg_addr) = args.expect_two()?;
+        let cond = self.get_value_from_arg(arg_cond)?;
+        let addr = self.get_value_from_arg(arg_addr)?;
 
         if op(cond) {
+            let addr = to_address(addr)?;
             self.jump_flag.replace(addr);
         }
+
+        Ok(())
     }
 
-    fn relative_base_operation(&mut self, args: Args) {
-        let arg_offset = args.expect_one("Error: invalid arguments for relative base operation");
-        let offset = self.get_value_from_arg(arg_offset);
+    fn relative_base_operation(&mut self, args: Args) -> Result<(), Fault> {
+        let arg_offset = args.expect_one()?;
+        let offset = self.get_value_from_arg(arg_offset)?;
+        let previous = self.relative_base;
 
-        self.relative_base += offset;
-    }
+        self.relative_base = previous
+            .checked_add(offset)
+            .ok_or(Fault::ArithmeticOverflow)?;
 
-    fn input_operation(&mut self, args: Args) {
-        let arg_addr = args.expect_one("Error: invalid arguments for input operation");
-        let addr = self.get_address_from_arg(arg_addr);
+        if let Some(entry) = self.pending_trace.as_mut() {
+            entry.relative_base = Some((previous, self.relative_base));
+        }
 
-        let input_value = self.io_provider.send_input();
-        self.try_write_or_resize(addr, input_value);
+        Ok(())
     }
 
-    fn output_operation(&mut self, args: Args) {
-        let arg_value = args.expect_one("Error: invalid arguments for output operation");
-        let output_value = self.get_value_from_arg(arg_value);
+    fn input_operation(&mut self, args: Args) -> Result<RunState, Fault> {
+        let arg_addr = args.expect_one()?;
+        let addr = self.get_address_from_arg(arg_addr)?;
 
-        self.io_provider.get_output(output_value);
-        self.last_output = Some(output_value);
+        match self.io_provider.try_send_input() {
+            Some(input_value) => {
+                if let Some(entry) = self.pending_trace.as_mut() {
+                    entry.input = Some(input_value);
+                }
 
-        if self.interrupt_on_output {
-            self.output_interrupted_flag = true;
+                self.try_write_or_resize(addr, input_value);
+                Ok(RunState::Running)
+            }
+            None => Ok(RunState::NeedsInput),
         }
     }
 
-    fn parse_instruction(&self) -> (Op, Args) {
-        let opcode_unparsed = self.memory[self.program_counter];
-        let code = opcode_unparsed % 100;
-
-        let operation = Op::from_code(code);
-        let n_args = operation.expected_n_args();
-        let args = self.get_args(n_args, opcode_unparsed / 100);
-        (operation, args)
-    }
+    fn output_operation(&mut self, args: Args) -> Result<RunState, Fault> {
+        let arg_value = args.expect_one()?;
+        let output_value = self.get_value_from_arg(arg_value)?;
 
-    fn get_args(&self, n_args: usize, modes: i64) -> Args {
-        let arg_begin = self.program_counter + 1;
-        let modes = get_modes(modes);
-        let mem_slice = self.memory[arg_begin..].iter().copied();
-        let mut args = izip!(modes, mem_slice).map(Arg::from_tuple);
-
-        match n_args {
-            3 => {
-                let (a, b, c) = args.next_tuple().unwrap();
-                Args::Three(a, b, c)
+        if let Some(max) = self.limits.max_outputs {
+            if self.outputs_produced >= max {
+                return Err(Fault::OutputLimit(max));
             }
-            2 => {
-                let (a, b) = args.next_tuple().unwrap();
-                Args::Two(a, b)
-            }
-            1 => {
-                let (a,) = args.next_tuple().unwrap();
-                Args::One(a)
-            }
-            0 => Args::Zero,
-            _ => panic!("Error: unrecognized argument number"),
         }
-    }
 
-    fn get_value_from_arg(&mut self, arg: Arg) -> i64 {
-        match arg {
-            Arg::Position(v) => self.try_read_or_resize(v as usize),
-            Arg::Immediate(v) => v,
-            Arg::Relative(v) => {
-                let relative_address = self.relative_base + v;
-                self.try_read_or_resize(relative_address as usize)
-            }
+        self.outputs_produced += 1;
+        self.io_provider.get_output(output_value);
+        self.last_output = Some(output_value);
+
+        if let Some(entry) = self.pending_trace.as_mut() {
+            entry.output = Some(output_value);
         }
+
+        Ok(RunState::Output(output_value))
     }
 
-    fn get_address_from_arg(&self, arg: Arg) -> usize {
-        match arg {
-            Arg::Position(v) => v as usize,
-            Arg::Relative(v) => (self.relative_base + v) as usize,
-            Arg::Immediate(_) => panic!("Error: write access at an address in immediate mode"),
+    fn get_value_from_arg(&mut self, arg: Arg) -> Result<i64, Fault> {
+        let value = match arg {
+            Arg::Position(v) => self.peek(to_address(v)?),
+            Arg::Immediate(v) => v,
+            Arg::Relative(v) => self.peek(self.relative_address(v)?),
+        };
+
+        if let Some(entry) = self.pending_trace.as_mut() {
+            entry.operands.push(value);
         }
-    }
 
-    fn try_read_or_resize(&mut self, position: usize) -> i64 {
-        self.resize_if_needed(position);
-        self.memory[position]
+        Ok(value)
     }
 
-    fn try_write_or_resize(&mut self, position: usize, value: i64) {
-        self.resize_if_needed(position);
-        self.memory[position] = value;
-    }
+    fn relative_address(&self, offset: i64) -> Result<usize, Fault> {
+        let address = self
+            .relative_base
+            .checked_add(offset)
+            .ok_or(Fault::ArithmeticOverflow)?;
 
-    fn resize_if_needed(&mut self, position: usize) {
-        let current_len = self.memory.len();
-        if position >= current_len {
-            let extension_size = position - current_len + 1;
-            self.extend_by(extension_size);
-        }
+        to_address(address)
     }
 
-    fn extend_by(&mut self, number: usize) {
-        let extender = std::iter::repeat(0).take(number);
+    fn get_address_from_arg(&mut self, arg: Arg) -> Result<usize, Fault> {
+        let address = match arg {
+            Arg::Position(v) => to_address(v)?,
+            Arg::Relative(v) => self.relative_address(v)?,
+            Arg::Immediate(_) => return Err(Fault::ImmediateWrite),
+        };
+
+        self.check_write(address)?;
+
+        if let Some(entry) = self.pending_trace.as_mut() {
+            entry.operands.push(address as i64);
+        }
 
-        self.memory.extend(extender);
+        Ok(address)
     }
 }
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::fmt;
+
+pub const PAGE_SIZE: usize = 1024;
+
+/// Storage for a machine's memory cells.
+///
+/// Every address is readable; cells that were never written read as zero.
+pub trait Memory: fmt::Debug + Send {
+    fn read(&self, address: usize) -> i64;
+
+    /// Stores `value`, allocating the cell if needed.
+    ///
+    /// `address` must be below `usize::MAX`, as `Machine` checks before writing.
+    fn write(&mut self, address: usize, value: i64);
+
+    /// One past the highest address backed by storage.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of cells backed by storage.
+    fn allocated(&self) -> usize;
+
+    /// Number of cells that would be backed by storage after writing to `address`.
+    fn allocated_after_write(&self, address: usize) -> usize;
+
+    /// Contiguous runs of stored cells as `(start address, values)`, in address order.
+    fn segments(&self) -> Vec<(usize, &[i64])>;
+
+    fn from_segments(segments: &[(usize, Vec<i64>)]) -> Self
+    where
+        Self: Sized;
+}
+
+/// Memory as one vector, grown up to the highest address written.
+///
+/// A single far write allocates every cell before it, so machines running programs
+/// that may write far addresses should use `PagedMemory` or set `Limits::max_memory`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DenseMemory {
+    cells: Vec<i64>,
+}
+
+/// Memory split into fixed-size pages, allocated only when written to,
+/// so programs can touch far-apart addresses without allocating the space between them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PagedMemory {
+    pages: HashMap<usize, Box<[i64]>>,
+    len: usize,
+}
+
+impl DenseMemory {
+    pub fn as_slice(&self) -> &[i64] {
+        &self.cells
+    }
+}
+
+impl From<Vec<i64>> for DenseMemory {
+    fn from(cells: Vec<i64>) -> Self {
+        DenseMemory { cells }
+    }
+}
+
+impl Memory for DenseMemory {
+    fn read(&self, address: usize) -> i64 {
+        self.cells.get(address).copied().unwrap_or(0)
+    }
+
+    fn write(&mut self, address: usize, value: i64) {
+        if address >= self.cells.len() {
+            let len = address.checked_add(1).expect("address out of range");
+            self.cells.resize(len, 0);
+        }
+
+        self.cells[address] = value;
+    }
+
+    fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn allocated(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn allocated_after_write(&self, address: usize) -> usize {
+        self.cells.len().max(address.saturating_add(1))
+    }
+
+    fn segments(&self) -> Vec<(usize, &[i64])> {
+        vec![(0, &self.cells)]
+    }
+
+    fn from_segments(segments: &[(usize, Vec<i64>)]) -> Self {
+        let mut memory = DenseMemory::default();
+
+        for (start, values) in segments.iter().filter(|(_, values)| !values.is_empty()) {
+            let end = start + values.len();
+
+            memory.write(end - 1, 0);
+            memory.cells[*start..end].copy_from_slice(values);
+        }
+
+        memory
+    }
+}
+
+impl From<Vec<i64>> for PagedMemory {
+    fn from(cells: Vec<i64>) -> Self {
+        PagedMemory::from_segments(&[(0, cells)])
+    }
+}
+
+impl Memory for PagedMemory {
+    fn read(&self, address: usize) -> i64 {
+        self.pages
+            .get(&(address / PAGE_SIZE))
+            .map_or(0, |page| page[address % PAGE_SIZE])
+    }
+
+    fn write(&mut self, address: usize, value: i64) {
+        let page = self
+            .pages
+            .entry(address / PAGE_SIZE)
+            .or_insert_with(|| vec![0; PAGE_SIZE].into_boxed_slice());
+
+        page[address % PAGE_SIZE] = value;
+        self.len = self.len.max(address.saturating_add(1));
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn allocated(&self) -> usize {
+        self.pages.len() * PAGE_SIZE
+    }
+
+    fn allocated_after_write(&self, address: usize) -> usize {
+        match self.pages.contains_key(&(address / PAGE_SIZE)) {
+            true => self.allocated(),
+            false => self.allocated() + PAGE_SIZE,
+        }
+    }
+
+    fn segments(&self) -> Vec<(usize, &[i64])> {
+        let mut indices: Vec<usize> = self.pages.keys().copied().collect();
+        indices.sort_unstable();
+
+        indices
+            .into_iter()
+            .map(|index| (index * PAGE_SIZE, &self.pages[&index][..]))
+            .collect()
+    }
+
+    fn from_segments(segments: &[(usize, Vec<i64>)]) -> Self {
+        let mut memory = PagedMemory::default();
+
+        for (start, values) in segments {
+            for (offset, &value) in values.iter().enumerate() {
+                memory.write(start + offset, value);
+            }
+        }
+
+        memory
+    }
+}
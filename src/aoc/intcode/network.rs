@@ -0,0 +1,255 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+
+use super::error::IntcodeError;
+use super::io::IoProvider;
+use super::machine::{Limits, Machine};
+
+/// An `(x, y)` pair sent to another address on a `Network`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packet {
+    pub destination: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+/// What a `Hook` wants the network to do next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookAction {
+    Continue,
+    Send(Packet),
+    Stop(i64),
+}
+
+/// Handles packets sent to an address that no machine owns, e.g. the NAT at 255.
+pub trait Hook {
+    fn on_packet(&mut self, packet: Packet) -> HookAction;
+
+    /// Called when every machine is waiting on an empty queue.
+    fn on_idle(&mut self) -> HookAction {
+        HookAction::Continue
+    }
+}
+
+impl<F: FnMut(Packet) -> HookAction> Hook for F {
+    fn on_packet(&mut self, packet: Packet) -> HookAction {
+        self(packet)
+    }
+}
+
+/// The NAT from day 23: remembers the last packet it received
+/// and resends it to address 0 whenever the network goes idle.
+/// Stops with the `y` it delivered twice in a row.
+#[derive(Debug, Default)]
+pub struct Nat {
+    last_received: Option<Packet>,
+    last_delivered_y: Option<i64>,
+}
+
+/// Runs one machine per address, connected by packet queues.
+///
+/// Every machine first receives its own address, then reads `-1` whenever its queue is empty.
+pub struct Network {
+    machines: Vec<Machine<Nic>>,
+    hooks: BTreeMap<i64, Box<dyn Hook>>,
+}
+
+#[derive(Debug, Default)]
+struct Nic {
+    inputs: VecDeque<i64>,
+    outputs: Vec<i64>,
+    polled_empty: bool,
+}
+
+impl Nat {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Hook for Nat {
+    fn on_packet(&mut self, packet: Packet) -> HookAction {
+        self.last_received = Some(packet);
+        HookAction::Continue
+    }
+
+    fn on_idle(&mut self) -> HookAction {
+        let packet = match self.last_received {
+            Some(packet) => packet,
+            None => return HookAction::Continue,
+        };
+
+        if self.last_delivered_y == Some(packet.y) {
+            return HookAction::Stop(packet.y);
+        }
+        self.last_delivered_y = Some(packet.y);
+
+        HookAction::Send(Packet {
+            destination: 0,
+            ..packet
+        })
+    }
+}
+
+impl IoProvider for Nic {
+    fn send_input(&mut self) -> i64 {
+        self.inputs.pop_front().unwrap_or(-1)
+    }
+
+    /// Reports an empty queue once per round, then pauses the machine
+    /// so the other machines get their turn.
+    fn try_send_input(&mut self) -> Option<i64> {
+        match self.inputs.pop_front() {
+            Some(value) => Some(value),
+            None if self.polled_empty => None,
+            None => {
+                self.polled_empty = true;
+                Some(-1)
+            }
+        }
+    }
+
+    fn get_output(&mut self, value: i64) {
+        self.outputs.push(value);
+    }
+}
+
+impl Network {
+    /// Boots `size` copies of `program` at addresses `0..size`.
+    pub fn new(program: &[i64], size: usize) -> Self {
+        let machines = (0..size as i64)
+            .map(|address| {
+                let nic = Nic {
+                    inputs: VecDeque::from([address]),
+                    ..Default::default()
+                };
+
+                Machine::with_provider(program.to_vec(), nic)
+            })
+            .collect();
+
+        Network {
+            machines,
+            hooks: BTreeMap::new(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.machines.len()
+    }
+
+    pub fn add_hook<H: Hook + 'static>(&mut self, address: i64, hook: H) {
+        self.hooks.insert(address, Box::new(hook));
+    }
+
+    /// Applies `limits` to every machine, bounding how long a single round can take.
+    pub fn set_limits(&mut self, limits: Limits) {
+        for machine in self.machines.iter_mut() {
+            machine.limits = limits;
+        }
+    }
+
+    /// Queues a packet for delivery, returning the result if a hook stops the network.
+    /// Packets to unknown addresses without a hook are dropped.
+    pub fn send(&mut self, packet: Packet) -> Option<i64> {
+        if let Some(machine) = self.machine_at(packet.destination) {
+            machine.provider_mut().inputs.extend([packet.x, packet.y]);
+            return None;
+        }
+
+        let action = self.hooks.get_mut(&packet.destination)?.on_packet(packet);
+        self.act(action)
+    }
+
+    /// Runs rounds until a hook stops the network,
+    /// or returns `None` once it is idle for good or all machines have halted.
+    pub fn run(&mut self) -> Result<Option<i64>, IntcodeError> {
+        loop {
+            if let Some(result) = self.round()? {
+                return Ok(Some(result));
+            }
+
+            if self.machines.iter().all(|m| m.is_halted()) {
+                return Ok(None);
+            }
+
+            if self.is_idle() {
+                let actions: Vec<HookAction> =
+                    self.hooks.values_mut().map(|hook| hook.on_idle()).collect();
+
+                if actions.iter().all(|&action| action == HookAction::Continue) {
+                    return Ok(None);
+                }
+                for action in actions {
+                    if let Some(result) = self.act(action) {
+                        return Ok(Some(result));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Gives every machine one turn, running it until it blocks on input or halts,
+    /// and delivers the packets sent meanwhile.
+    fn round(&mut self) -> Result<Option<i64>, IntcodeError> {
+        for address in 0..self.machines.len() {
+            let machine = &mut self.machines[address];
+            machine.provider_mut().polled_empty = false;
+
+            machine.run()?;
+
+            let outputs = &mut self.machines[address].provider_mut().outputs;
+            let complete = outputs.len() - outputs.len() % 3;
+            let packets: Vec<Packet> = outputs
+                .drain(..complete)
+                .collect::<Vec<_>>()
+                .chunks(3)
+                .map(|chunk| Packet {
+                    destination: chunk[0],
+                    x: chunk[1],
+                    y: chunk[2],
+                })
+                .collect();
+
+            for packet in packets {
+                if let Some(result) = self.send(packet) {
+                    return Ok(Some(result));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Whether every machine has halted or found its queue empty, with nothing left in flight.
+    pub fn is_idle(&self) -> bool {
+        self.machines.iter().all(|machine| {
+            let nic = machine.provider();
+
+            machine.is_halted()
+                || (nic.polled_empty && nic.inputs.is_empty() && nic.outputs.is_empty())
+        })
+    }
+
+    fn act(&mut self, action: HookAction) -> Option<i64> {
+        match action {
+            HookAction::Continue => None,
+            HookAction::Send(packet) => self.send(packet),
+            HookAction::Stop(result) => Some(result),
+        }
+    }
+
+    fn machine_at(&mut self, address: i64) -> Option<&mut Machine<Nic>> {
+        let index = usize::try_from(address).ok()?;
+        self.machines.get_mut(index)
+    }
+}
+
+impl fmt::Debug for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Network")
+            .field("machines", &self.machines)
+            .field("hooks", &self.hooks.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,83 @@
+use super::error::IntcodeError;
+use super::io::QueueProvider;
+use super::machine::{Machine, RunState};
+
+/// A chain of machines, each stage's outputs feeding the next stage's inputs.
+///
+/// With `feedback` set, the last stage also feeds the first one,
+/// and the chain keeps cycling until the last stage halts.
+#[derive(Debug)]
+pub struct Pipeline {
+    stages: Vec<Machine<QueueProvider>>,
+    pub feedback: bool,
+}
+
+impl Pipeline {
+    /// Builds one stage per phase setting, each running a copy of `program`
+    /// with its phase as the first input.
+    pub fn new(program: &[i64], phases: &[i64]) -> Self {
+        let stages = phases
+            .iter()
+            .map(|&phase| {
+                Machine::with_provider(program.to_vec(), QueueProvider::with_inputs([phase]))
+            })
+            .collect();
+
+        Pipeline::from_stages(stages)
+    }
+
+    /// Chains already configured machines, e.g. running different programs
+    /// or with several initial inputs queued.
+    pub fn from_stages(stages: Vec<Machine<QueueProvider>>) -> Self {
+        Pipeline {
+            stages,
+            feedback: false,
+        }
+    }
+
+    pub fn stages(&self) -> &[Machine<QueueProvider>] {
+        &self.stages
+    }
+
+    /// Feeds `input` to the first stage and runs the chain,
+    /// returning the last value output by the last stage.
+    ///
+    /// A feedback loop also stops when no stage can make progress.
+    pub fn run(&mut self, input: i64) -> Result<Option<i64>, IntcodeError> {
+        let n_stages = self.stages.len();
+        if n_stages == 0 {
+            return Ok(Some(input));
+        }
+
+        self.stages[0].provider_mut().push_input(input);
+        let mut result = None;
+
+        loop {
+            let mut moved_any = false;
+
+            for index in 0..n_stages {
+                let state = self.stages[index].run()?;
+                let outputs = self.stages[index].provider_mut().drain_outputs();
+                let is_last = index + 1 == n_stages;
+
+                if is_last {
+                    result = outputs.last().copied().or(result);
+                }
+
+                moved_any |= !outputs.is_empty();
+                if !is_last || self.feedback {
+                    let next = (index + 1) % n_stages;
+                    self.stages[next].provider_mut().push_inputs(outputs);
+                }
+
+                if is_last && (state == RunState::Halted || !self.feedback) {
+                    return Ok(result);
+                }
+            }
+
+            if !moved_any {
+                return Ok(result);
+            }
+        }
+    }
+}
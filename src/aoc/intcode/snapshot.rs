@@ -0,0 +1,205 @@
+use std::io::{BufRead, Error, ErrorKind, Write};
+
+use itertools::Itertools;
+
+const HEADER: &str = "intcode-snapshot";
+/// Version 2 added `outputs_produced`.
+const VERSION: u32 = 2;
+/// Memory in a saved snapshot must end below this address,
+/// so loading one into `DenseMemory` allocates at most 512 MiB.
+const MAX_MEMORY_END: usize = 1 << 26;
+
+/// The complete execution state of a `Machine`, apart from its I/O provider.
+///
+/// Snapshots can be written to and read from a line-based text format:
+///
+/// ```text
+/// intcode-snapshot 2
+/// program_counter 4
+/// relative_base 0
+/// halted false
+/// interrupt_on_output false
+/// last_output none
+/// instructions_executed 2
+/// outputs_produced 0
+/// memory 1101,1,2,5,99,3
+/// ```
+///
+/// Version 1 snapshots, saved before `outputs_produced` existed, are still read,
+/// with no outputs produced.
+///
+/// Memory stored away from address zero, as by `PagedMemory`,
+/// gets one extra `memory@START v1,v2,...` line per segment;
+/// `PagedMemory` writes one per allocated page, padded with zeros.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub(crate) segments: Vec<(usize, Vec<i64>)>,
+    pub(crate) program_counter: usize,
+    pub(crate) relative_base: i64,
+    pub(crate) halted: bool,
+    pub(crate) interrupt_on_output: bool,
+    pub(crate) last_output: Option<i64>,
+    pub(crate) instructions_executed: u64,
+    pub(crate) outputs_produced: u64,
+}
+
+impl Snapshot {
+    /// A fresh state about to execute at `program_counter` over `memory`,
+    /// for resuming execution that began outside a `Machine`.
+    pub fn new(memory: Vec<i64>, program_counter: usize, relative_base: i64) -> Self {
+        Snapshot {
+            segments: vec![(0, memory)],
+            program_counter,
+            relative_base,
+            halted: false,
+            interrupt_on_output: false,
+            last_output: None,
+            instructions_executed: 0,
+            outputs_produced: 0,
+        }
+    }
+
+    /// Reads a memory cell as it was when the snapshot was taken.
+    pub fn read(&self, address: usize) -> i64 {
+        self.segments
+            .iter()
+            .find(|(start, values)| (*start..start + values.len()).contains(&address))
+            .map_or(0, |(start, values)| values[address - start])
+    }
+
+    pub fn program_counter(&self) -> usize {
+        self.program_counter
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn write_to<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let last_output = self
+            .last_output
+            .map_or_else(|| "none".to_string(), |v| v.to_string());
+
+        writeln!(writer, "{} {}", HEADER, VERSION)?;
+        writeln!(writer, "program_counter {}", self.program_counter)?;
+        writeln!(writer, "relative_base {}", self.relative_base)?;
+        writeln!(writer, "halted {}", self.halted)?;
+        writeln!(writer, "interrupt_on_output {}", self.interrupt_on_output)?;
+        writeln!(writer, "last_output {}", last_output)?;
+        writeln!(
+            writer,
+            "instructions_executed {}",
+            self.instructions_executed
+        )?;
+        writeln!(writer, "outputs_produced {}", self.outputs_produced)?;
+        for (start, values) in &self.segments {
+            match start {
+                0 => writeln!(writer, "memory {}", values.iter().join(","))?,
+                _ => writeln!(writer, "memory@{} {}", start, values.iter().join(","))?,
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn read_from<R: BufRead>(reader: R) -> std::io::Result<Self> {
+        let mut lines = reader.lines();
+
+        let version: u32 = match lines.next() {
+            Some(Ok(header)) => match header.trim().split_once(' ') {
+                Some((HEADER, version)) => parse(version)?,
+                _ => return Err(invalid("missing snapshot header")),
+            },
+            Some(Err(err)) => return Err(err),
+            None => return Err(invalid("missing snapshot header")),
+        };
+        if !(1..=VERSION).contains(&version) {
+            return Err(invalid(&format!(
+                "unsupported snapshot version {}",
+                version
+            )));
+        }
+
+        let mut fields = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once(' ')
+                .ok_or_else(|| invalid("malformed snapshot line"))?;
+            fields.push((key.to_string(), value.trim().to_string()));
+        }
+
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+                .ok_or_else(|| invalid(&format!("missing snapshot field `{}`", name)))
+        };
+
+        let segments = fields
+            .iter()
+            .filter_map(|(key, values)| match key.as_str() {
+                "memory" => Some(("0", values)),
+                key => key.strip_prefix("memory@").map(|start| (start, values)),
+            })
+            .map(|(start, values)| segment(parse(start)?, parse_values(values)?))
+            .collect::<std::io::Result<_>>()?;
+        let outputs_produced = match (version, field("outputs_produced")) {
+            (_, Ok(value)) => parse(value)?,
+            (1, Err(_)) => 0,
+            (_, Err(err)) => return Err(err),
+        };
+        let last_output = match field("last_output")? {
+            "none" => None,
+            value => Some(parse(value)?),
+        };
+
+        Ok(Snapshot {
+            segments,
+            program_counter: parse(field("program_counter")?)?,
+            relative_base: parse(field("relative_base")?)?,
+            halted: parse(field("halted")?)?,
+            interrupt_on_output: parse(field("interrupt_on_output")?)?,
+            last_output,
+            instructions_executed: parse(field("instructions_executed")?)?,
+            outputs_produced,
+        })
+    }
+}
+
+fn parse<F: std::str::FromStr>(value: &str) -> std::io::Result<F> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| invalid(&format!("invalid snapshot value `{}`", value)))
+}
+
+fn segment(start: usize, values: Vec<i64>) -> std::io::Result<(usize, Vec<i64>)> {
+    match start.checked_add(values.len()) {
+        Some(end) if end <= MAX_MEMORY_END => Ok((start, values)),
+        _ => Err(invalid(&format!(
+            "snapshot memory must end below address {}",
+            MAX_MEMORY_END
+        ))),
+    }
+}
+
+fn parse_values(values: &str) -> std::io::Result<Vec<i64>> {
+    match values {
+        "" => Ok(Vec::new()),
+        values => values.split(',').map(parse).collect(),
+    }
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, msg)
+}
@@ -0,0 +1,251 @@
+use std::any::Any;
+use std::fmt;
+use std::io::Write;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+use itertools::Itertools;
+
+use super::decode::{Args, Op};
+
+/// One executed instruction, as seen by a `Tracer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// Index of the instruction among all instructions executed by the machine.
+    pub step: u64,
+    pub program_counter: usize,
+    pub op: Op,
+    pub args: Args,
+    /// Resolved arguments: values for the ones read, addresses for the one written.
+    pub operands: Vec<i64>,
+    /// Address and new value of the memory cell written, if any.
+    pub write: Option<(usize, i64)>,
+    /// Relative base before and after an `ARB` instruction.
+    pub relative_base: Option<(i64, i64)>,
+    pub input: Option<i64>,
+    pub output: Option<i64>,
+}
+
+/// Receives every instruction executed by a machine it is attached to.
+pub trait Tracer: Any + Send + fmt::Debug {
+    fn trace(&mut self, entry: &TraceEntry);
+}
+
+/// Keeps trace entries in memory.
+///
+/// Clones share the same buffer, so one clone can be handed to a machine
+/// while another is used to read the entries back.
+#[derive(Debug, Clone, Default)]
+pub struct TraceRecorder {
+    entries: Arc<Mutex<Vec<TraceEntry>>>,
+    filter: TraceFilter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    JsonLines,
+    Text,
+}
+
+/// Selects trace entries by instruction address and operation.
+/// Empty criteria match everything.
+#[derive(Debug, Clone, Default)]
+pub struct TraceFilter {
+    pub addresses: Option<Range<usize>>,
+    pub ops: Vec<Op>,
+}
+
+/// Streams trace entries to a writer as they are produced.
+#[derive(Debug)]
+pub struct TraceWriter<W> {
+    writer: W,
+    format: TraceFormat,
+    filter: TraceFilter,
+    error: Option<std::io::Error>,
+}
+
+impl TraceEntry {
+    pub(crate) fn new(step: u64, program_counter: usize, op: Op, args: Args) -> Self {
+        TraceEntry {
+            step,
+            program_counter,
+            op,
+            args,
+            operands: Vec::with_capacity(args.len()),
+            write: None,
+            relative_base: None,
+            input: None,
+            output: None,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let args = self
+            .args
+            .to_vec()
+            .iter()
+            .map(|arg| format!("\"{}\"", arg))
+            .join(",");
+        let write = self.write.map_or_else(
+            || "null".to_string(),
+            |(address, value)| format!("{{\"address\":{},\"value\":{}}}", address, value),
+        );
+        let relative_base = self.relative_base.map_or_else(
+            || "null".to_string(),
+            |(from, to)| format!("{{\"from\":{},\"to\":{}}}", from, to),
+        );
+
+        format!(
+            "{{\"step\":{},\"pc\":{},\"op\":\"{}\",\"args\":[{}],\"operands\":[{}],\
+             \"write\":{},\"rb\":{},\"input\":{},\"output\":{}}}",
+            self.step,
+            self.program_counter,
+            self.op,
+            args,
+            self.operands.iter().join(","),
+            write,
+            relative_base,
+            json_option(self.input),
+            json_option(self.output),
+        )
+    }
+
+    pub fn to_text(&self) -> String {
+        let instruction = match self.args {
+            Args::Zero => self.op.to_string(),
+            args => format!("{} {}", self.op, args.to_vec().iter().join(", ")),
+        };
+
+        let mut effects = Vec::new();
+        if let Some((address, value)) = self.write {
+            effects.push(format!("[{}] <- {}", address, value));
+        }
+        if let Some((from, to)) = self.relative_base {
+            effects.push(format!("rb {} -> {}", from, to));
+        }
+        if let Some(value) = self.input {
+            effects.push(format!("in {}", value));
+        }
+        if let Some(value) = self.output {
+            effects.push(format!("out {}", value));
+        }
+
+        let line = format!(
+            "{:>8} {:>6}: {:<32} ({})",
+            self.step,
+            self.program_counter,
+            instruction,
+            self.operands.iter().join(", ")
+        );
+
+        if effects.is_empty() {
+            line
+        } else {
+            format!("{} {}", line, effects.join("; "))
+        }
+    }
+
+    pub fn format(&self, format: TraceFormat) -> String {
+        match format {
+            TraceFormat::JsonLines => self.to_json(),
+            TraceFormat::Text => self.to_text(),
+        }
+    }
+}
+
+fn json_option(value: Option<i64>) -> String {
+    value.map_or_else(|| "null".to_string(), |v| v.to_string())
+}
+
+impl TraceFilter {
+    pub fn matches(&self, entry: &TraceEntry) -> bool {
+        let address_matches = self
+            .addresses
+            .as_ref()
+            .is_none_or(|range| range.contains(&entry.program_counter));
+        let op_matches = self.ops.is_empty() || self.ops.contains(&entry.op);
+
+        address_matches && op_matches
+    }
+}
+
+impl TraceRecorder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_filter(filter: TraceFilter) -> Self {
+        TraceRecorder {
+            entries: Default::default(),
+            filter,
+        }
+    }
+
+    pub fn entries(&self) -> Vec<TraceEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+impl Tracer for TraceRecorder {
+    fn trace(&mut self, entry: &TraceEntry) {
+        if self.filter.matches(entry) {
+            self.entries.lock().unwrap().push(entry.clone());
+        }
+    }
+}
+
+impl<W: Write> TraceWriter<W> {
+    pub fn new(writer: W, format: TraceFormat) -> Self {
+        Self::with_filter(writer, format, Default::default())
+    }
+
+    pub fn with_filter(writer: W, format: TraceFormat, filter: TraceFilter) -> Self {
+        TraceWriter {
+            writer,
+            format,
+            filter,
+            error: None,
+        }
+    }
+
+    /// Flushes the writer and returns it,
+    /// reporting the first error encountered while tracing.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write + Send + fmt::Debug + 'static> Tracer for TraceWriter<W> {
+    fn trace(&mut self, entry: &TraceEntry) {
+        if self.error.is_some() || !self.filter.matches(entry) {
+            return;
+        }
+
+        if let Err(err) = writeln!(self.writer, "{}", entry.format(self.format)) {
+            self.error = Some(err);
+        }
+    }
+}
+
+/// Writes already recorded entries that pass `filter`, one per line.
+pub fn write_trace<W: Write>(
+    entries: &[TraceEntry],
+    format: TraceFormat,
+    filter: &TraceFilter,
+    mut writer: W,
+) -> std::io::Result<()> {
+    for entry in entries.iter().filter(|e| filter.matches(e)) {
+        writeln!(writer, "{}", entry.format(format))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,273 @@
+//! Ahead-of-time translation of Intcode programs into Rust source.
+//!
+//! The generated module defines a `Program` type with one compiled block per instruction
+//! found by `disassemble`. It runs natively until execution reaches an address without
+//! a block or an instruction whose words were overwritten, and from then on hands its
+//! state over to an embedded `Machine`.
+
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use super::decode::{Arg, Op};
+use super::disasm::{disassemble, encode, Instruction};
+
+const PRELUDE: &str = r#"const NONE: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    memory: Vec<i64>,
+    stale: Vec<bool>,
+    pc: usize,
+    rb: i64,
+    halted: bool,
+    last_output: Option<i64>,
+    interpreted: bool,
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Program::new()
+    }
+}
+
+impl Program {
+    pub fn new() -> Self {
+        Program {
+            memory: PROGRAM.to_vec(),
+            stale: vec![false; PROGRAM.len()],
+            pc: 0,
+            rb: 0,
+            halted: false,
+            last_output: None,
+            interpreted: false,
+        }
+    }
+
+    pub fn read(&self, address: usize) -> i64 {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    pub fn last_output(&self) -> Option<i64> {
+        self.last_output
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Whether execution has been handed over to the interpreter.
+    pub fn is_interpreted(&self) -> bool {
+        self.interpreted
+    }
+
+    fn address(&self, value: i64, instruction: i64) -> Result<usize, IntcodeError> {
+        usize::try_from(value).map_err(|_| IntcodeError::NegativeAddress {
+            program_counter: self.pc,
+            instruction,
+            address: value,
+        })
+    }
+
+    fn checked(&self, result: Option<i64>, instruction: i64) -> Result<i64, IntcodeError> {
+        result.ok_or(IntcodeError::ArithmeticOverflow {
+            program_counter: self.pc,
+            instruction,
+        })
+    }
+
+    fn store(&mut self, address: usize, value: i64) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+
+        if let Some(&owner) = OWNER.get(address) {
+            if owner != NONE && self.memory[address] != value {
+                self.stale[owner] = true;
+            }
+        }
+        self.memory[address] = value;
+    }
+
+    fn interpret<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        self.interpreted = true;
+
+        let memory = std::mem::take(&mut self.memory);
+        let mut machine = Machine::from_snapshot(&Snapshot::new(memory, self.pc, self.rb), io);
+        let result = machine.run();
+
+        self.memory = machine.memory().as_slice().to_vec();
+        self.pc = machine.program_counter();
+        self.rb = machine.relative_base();
+        self.halted = machine.is_halted();
+        self.last_output = machine.last_output().or(self.last_output);
+
+        result
+    }
+"#;
+
+/// Translates `program` into the source of a Rust module exposing `Program`,
+/// whose `run` behaves like `Machine::run` with the same provider.
+pub fn transpile(program: &[i64]) -> String {
+    let instructions = disassemble(program);
+    let mut owner = vec![None; program.len()];
+    for instruction in instructions.iter() {
+        if let Instruction::Op { address, .. } = *instruction {
+            let words = address..address + instruction.n_words();
+            owner[words].fill(Some(address));
+        }
+    }
+
+    let mut source = String::new();
+    writeln!(source, "// Generated by intcode-transpile; do not edit.\n").unwrap();
+    writeln!(
+        source,
+        "use aoc::intcode::{{IntcodeError, IoProvider, Machine, RunState, Snapshot}};\n"
+    )
+    .unwrap();
+    writeln!(
+        source,
+        "const PROGRAM: [i64; {}] = [{}];\n",
+        program.len(),
+        program.iter().join(", ")
+    )
+    .unwrap();
+    writeln!(
+        source,
+        "// Start address of the compiled instruction each program word belongs to.\n\
+         const OWNER: [usize; {}] = [{}];\n",
+        program.len(),
+        owner
+            .iter()
+            .map(|start| start.map_or("NONE".to_string(), |s| s.to_string()))
+            .join(", ")
+    )
+    .unwrap();
+    source.push_str(PRELUDE);
+
+    source.push_str(
+        "
+    /// Runs until the program halts or its provider has no input ready.
+    pub fn run<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        if self.halted {
+            return Ok(RunState::Halted);
+        }
+
+        loop {
+            if self.interpreted || self.stale.get(self.pc) == Some(&true) {
+                return self.interpret(io);
+            }
+
+            match self.pc {
+",
+    );
+
+    for instruction in instructions.iter() {
+        if let Instruction::Op { address, op, args } = *instruction {
+            let word = encode(op, &args)[0];
+            let next = address + instruction.n_words();
+
+            writeln!(source, "                {} => {{", address).unwrap();
+            writeln!(source, "                    // {}", instruction).unwrap();
+            for line in compile(op, &args.to_vec(), word, next) {
+                writeln!(source, "                    {}", line).unwrap();
+            }
+            writeln!(source, "                }}").unwrap();
+        }
+    }
+
+    source.push_str(
+        "                _ => return self.interpret(io),
+            }
+        }
+    }
+}
+",
+    );
+
+    source
+}
+
+fn compile(op: Op, args: &[Arg], word: i64, next: usize) -> Vec<String> {
+    let value = |name: &str, arg: Arg| match arg {
+        Arg::Immediate(v) => format!("let {} = {};", name, v),
+        Arg::Position(p) if p >= 0 => format!("let {} = self.read({});", name, p),
+        Arg::Position(p) => format!("let {} = self.read(self.address({}, {})?);", name, p, word),
+        Arg::Relative(o) => format!(
+            "let {} = self.read(self.address({}, {})?);",
+            name,
+            relative(o, word),
+            word
+        ),
+    };
+    let address = |arg: Arg| match arg {
+        Arg::Position(p) if p >= 0 => format!("let d = {};", p),
+        Arg::Position(p) => format!("let d = self.address({}, {})?;", p, word),
+        Arg::Relative(o) => format!("let d = self.address({}, {})?;", relative(o, word), word),
+        Arg::Immediate(_) => unreachable!("decoder rejects immediate destinations"),
+    };
+    let advance = format!("self.pc = {};", next);
+
+    match op {
+        Op::Add | Op::Mul | Op::Tlt | Op::Teq => {
+            let result = match op {
+                Op::Add => format!("self.checked(i64::checked_add(a, b), {})?", word),
+                Op::Mul => format!("self.checked(i64::checked_mul(a, b), {})?", word),
+                Op::Tlt => "i64::from(a < b)".to_string(),
+                _ => "i64::from(a == b)".to_string(),
+            };
+
+            vec![
+                value("a", args[0]),
+                value("b", args[1]),
+                address(args[2]),
+                format!("self.store(d, {});", result),
+                advance,
+            ]
+        }
+        Op::Inp => vec![
+            address(args[0]),
+            "match io.try_send_input() {".to_string(),
+            "    Some(v) => self.store(d, v),".to_string(),
+            "    None => return Ok(RunState::NeedsInput),".to_string(),
+            "}".to_string(),
+            advance,
+        ],
+        Op::Out => vec![
+            value("a", args[0]),
+            "io.get_output(a);".to_string(),
+            "self.last_output = Some(a);".to_string(),
+            advance,
+        ],
+        Op::Jnz | Op::Jez => {
+            let condition = if op == Op::Jnz { "a != 0" } else { "a == 0" };
+
+            vec![
+                value("a", args[0]),
+                value("b", args[1]),
+                format!("if {} {{", condition),
+                format!("    self.pc = self.address(b, {})?;", word),
+                "} else {".to_string(),
+                format!("    {}", advance),
+                "}".to_string(),
+            ]
+        }
+        Op::Rel => vec![
+            value("a", args[0]),
+            format!("self.rb = self.checked(self.rb.checked_add(a), {})?;", word),
+            advance,
+        ],
+        Op::Hlt => vec![
+            advance,
+            "self.halted = true;".to_string(),
+            "return Ok(RunState::Halted);".to_string(),
+        ],
+    }
+}
+
+fn relative(offset: i64, word: i64) -> String {
+    match offset {
+        0 => "self.rb".to_string(),
+        o => format!("self.checked(self.rb.checked_add({}), {})?", o, word),
+    }
+}
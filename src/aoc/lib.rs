@@ -1,2 +1,7 @@
+pub mod days;
+pub mod grid;
+pub mod image;
 pub mod intcode;
+pub mod ocr;
+pub mod solution;
 pub mod utils;
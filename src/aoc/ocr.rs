@@ -0,0 +1,134 @@
+//! Reading the block capitals some puzzles draw as their answer.
+//!
+//! The letters are 6 cells tall, mostly 4 wide, and separated by at least one unlit column.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use itertools::Itertools;
+
+use super::grid::{Bounds, Point};
+use super::utils::invalid_input;
+
+const GLYPH_HEIGHT: usize = 6;
+
+/// Known glyphs, with `#` for lit cells.
+const GLYPHS: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    NoLitCells,
+    /// The lit cells span this many rows instead of the height of a letter.
+    WrongHeight(usize),
+    /// A glyph, drawn with `#` and `.`, starting this many columns from the left edge.
+    UnknownGlyph {
+        column: usize,
+        glyph: String,
+    },
+}
+
+/// Reads the letters drawn by `lit` cells, left to right.
+pub fn recognize<I: IntoIterator<Item = Point>>(lit: I) -> Result<String, OcrError> {
+    let lit: HashSet<Point> = lit.into_iter().collect();
+    let bounds = Bounds::of(lit.iter().copied()).ok_or(OcrError::NoLitCells)?;
+    if bounds.height() != GLYPH_HEIGHT {
+        return Err(OcrError::WrongHeight(bounds.height()));
+    }
+
+    let column_is_lit =
+        |x: i64| (bounds.min.y..=bounds.max.y).any(|y| lit.contains(&Point::new(x, y)));
+    let groups = (bounds.min.x..=bounds.max.x).group_by(|&x| column_is_lit(x));
+
+    groups
+        .into_iter()
+        .filter(|(is_lit, _)| *is_lit)
+        .map(|(_, columns)| {
+            let columns: Vec<i64> = columns.collect();
+            let glyph = (bounds.min.y..=bounds.max.y)
+                .map(|y| {
+                    columns
+                        .iter()
+                        .map(|&x| match lit.contains(&Point::new(x, y)) {
+                            true => '#',
+                            false => '.',
+                        })
+                        .collect::<String>()
+                })
+                .join("\n");
+
+            match GLYPHS.iter().find(|(_, known)| trim(known) == glyph) {
+                Some(&(letter, _)) => Ok(letter),
+                None => Err(OcrError::UnknownGlyph {
+                    column: (columns[0] - bounds.min.x) as usize,
+                    glyph,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Reads the letters from a picture with `#` for lit cells and anything else for unlit ones.
+pub fn recognize_text(picture: &str) -> Result<String, OcrError> {
+    let lit = picture.lines().enumerate().flat_map(|(y, line)| {
+        line.chars()
+            .enumerate()
+            .filter(|&(_, c)| c == '#')
+            .map(move |(x, _)| Point::new(x as i64, y as i64))
+    });
+
+    recognize(lit)
+}
+
+/// Removes the unlit columns around a known glyph, to compare it with a segmented one.
+fn trim(glyph: &str) -> String {
+    let rows: Vec<&str> = glyph.lines().collect();
+    let lit_columns: Vec<usize> = (0..rows[0].len())
+        .filter(|&x| rows.iter().any(|row| row.as_bytes()[x] == b'#'))
+        .collect();
+    let (first, last) = (lit_columns[0], lit_columns[lit_columns.len() - 1]);
+
+    rows.iter().map(|row| &row[first..=last]).join("\n")
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::NoLitCells => write!(f, "nothing to read, no cell is lit"),
+            OcrError::WrongHeight(height) => write!(
+                f,
+                "letters are {} cells tall, found {}",
+                GLYPH_HEIGHT, height
+            ),
+            OcrError::UnknownGlyph { column, glyph } => {
+                write!(f, "unknown glyph at column {}:\n{}", column, glyph)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+impl From<OcrError> for std::io::Error {
+    fn from(err: OcrError) -> Self {
+        invalid_input(err)
+    }
+}
@@ -0,0 +1,106 @@
+//! The interface shared by every puzzle solution, so they can be run by one program.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    /// Codes, letters or, when they cannot be read, a picture spanning several lines.
+    Text(String),
+}
+
+pub trait Solution: Sync {
+    fn day(&self) -> u32;
+
+    fn part(&self) -> u32;
+
+    fn title(&self) -> &'static str;
+
+    /// Solves the puzzle for the whole puzzle input.
+    fn solve(&self, input: &str) -> std::io::Result<Answer>;
+
+    /// Heading of the puzzle, e.g. `Day 7: Amplification Circuit - Part 2`.
+    fn description(&self) -> String {
+        format!(
+            "Day {}: {} - Part {}",
+            self.day(),
+            self.title(),
+            self.part()
+        )
+    }
+}
+
+/// A solution made of a plain function.
+pub struct Puzzle {
+    day: u32,
+    part: u32,
+    title: &'static str,
+    solve: fn(&str) -> std::io::Result<Answer>,
+}
+
+impl Puzzle {
+    pub const fn new(
+        day: u32,
+        part: u32,
+        title: &'static str,
+        solve: fn(&str) -> std::io::Result<Answer>,
+    ) -> Self {
+        Puzzle {
+            day,
+            part,
+            title,
+            solve,
+        }
+    }
+}
+
+impl Solution for Puzzle {
+    fn day(&self) -> u32 {
+        self.day
+    }
+
+    fn part(&self) -> u32 {
+        self.part
+    }
+
+    fn title(&self) -> &'static str {
+        self.title
+    }
+
+    fn solve(&self, input: &str) -> std::io::Result<Answer> {
+        (self.solve)(input)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
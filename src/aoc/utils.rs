@@ -1,18 +1,32 @@
-use std::io::{Error, ErrorKind};
+use std::io::{BufRead, Error, ErrorKind};
 
 pub use aoc_utils::BufferedInput;
 
 pub fn parse_intcode_program(description: &str) -> std::io::Result<Vec<i64>> {
     let input = BufferedInput::parse_args(description)?;
+
+    read_intcode_program(input)
+}
+
+pub fn read_intcode_program<R: BufRead>(input: R) -> std::io::Result<Vec<i64>> {
     let line = input
-        .unwrapped_lines()
+        .lines()
         .next()
-        .ok_or_else(|| Error::new(ErrorKind::Other, "Input has no content"))?;
+        .ok_or_else(|| Error::other("Input has no content"))??;
 
-    let result = line
+    line.trim()
         .split(',')
-        .map(|s| s.parse().expect("Failed to parse intcode program"))
-        .collect();
+        .map(|s| {
+            s.parse()
+                .map_err(|_| invalid_input(format!("Invalid intcode value `{}`", s)))
+        })
+        .collect()
+}
 
-    Ok(result)
+/// An `InvalidData` error, for puzzle input that doesn't have the expected shape.
+pub fn invalid_input<E>(error: E) -> Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    Error::new(ErrorKind::InvalidData, error)
 }
@@ -1,214 +1,80 @@
-use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
 
-use itertools::Itertools;
-use itertools::MinMaxResult::MinMax;
+use clap::{App, Arg};
 
-use aoc::intcode::{IoProvider, Machine};
-use aoc::utils::parse_intcode_program;
-
-#[macro_use]
-extern crate maplit;
-
-#[derive(Clone, Copy)]
-enum Color {
-    Black,
-    White,
-}
-
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-enum InstructionState {
-    AwaitingColor,
-    AwaitingTurn,
-}
-
-struct Walker {
-    x: i64,
-    y: i64,
-    facing: Direction,
-}
-
-impl Color {
-    pub fn from_value(val: i64) -> Self {
-        match val {
-            0 => Color::Black,
-            1 => Color::White,
-            _ => panic!("Invalid color value"),
-        }
-    }
-
-    pub fn value(&self) -> i64 {
-        match self {
-            Color::Black => 0,
-            Color::White => 1,
-        }
-    }
-}
-
-impl Direction {
-    pub fn turn_left(&mut self) {
-        *self = match self {
-            Direction::North => Direction::West,
-            Direction::West => Direction::South,
-            Direction::South => Direction::East,
-            Direction::East => Direction::North,
-        };
-    }
-
-    pub fn turn_right(&mut self) {
-        *self = match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        }
-    }
-}
-
-impl InstructionState {
-    pub fn advance(&mut self) {
-        *self = match self {
-            InstructionState::AwaitingColor => InstructionState::AwaitingTurn,
-            InstructionState::AwaitingTurn => InstructionState::AwaitingColor,
-        }
-    }
-}
-
-impl Walker {
-    pub fn new() -> Self {
-        Walker {
-            x: 0,
-            y: 0,
-            facing: Direction::North,
-        }
-    }
-
-    pub fn walk(&mut self) {
-        match self.facing {
-            Direction::North => self.y += 1,
-            Direction::South => self.y -= 1,
-            Direction::East => self.x += 1,
-            Direction::West => self.x -= 1,
-        }
-    }
-
-    pub fn turn_left(&mut self) {
-        self.facing.turn_left();
-    }
-
-    pub fn turn_right(&mut self) {
-        self.facing.turn_right();
-    }
-
-    pub fn position(&self) -> (i64, i64) {
-        (self.x, self.y)
-    }
-}
-
-struct PainterBot {
-    grid: HashMap<(i64, i64), Color>,
-    walker: Walker,
-    state: InstructionState,
-}
-
-impl PainterBot {
-    pub fn new() -> Self {
-        PainterBot {
-            grid: hashmap! { (0, 0) => Color::White },
-            walker: Walker::new(),
-            state: InstructionState::AwaitingColor,
-        }
-    }
-
-    pub fn grid(&self) -> &HashMap<(i64, i64), Color> {
-        &self.grid
-    }
-
-    fn current_color(&self) -> Color {
-        let pos = &self.walker.position();
-
-        self.grid.get(pos).copied().unwrap_or(Color::Black)
-    }
-
-    fn read_instruction(&mut self, instr: i64) {
-        match self.state {
-            InstructionState::AwaitingColor => {
-                let pos = self.walker.position();
-
-                self.grid.insert(pos, Color::from_value(instr));
-            }
-            InstructionState::AwaitingTurn => {
-                match instr {
-                    0 => self.walker.turn_left(),
-                    1 => self.walker.turn_right(),
-                    _ => panic!("Invalid direction instruction from CPU"),
-                };
-
-                self.walker.walk();
-            }
-        };
-
-        self.state.advance();
-    }
-}
-
-impl IoProvider for PainterBot {
-    fn send_input(&mut self) -> i64 {
-        self.current_color().value()
-    }
-
-    fn get_output(&mut self, value: i64) {
-        self.read_instruction(value);
-    }
-}
+use aoc::days::d11::{paint_and_export, ExportOptions, Palette};
+use aoc::image::Rgb;
+use aoc::utils::read_intcode_program;
 
 fn main() -> std::io::Result<()> {
-    let program = parse_intcode_program("Day 11: Space Police - Part 2")?;
-
-    let bot = &mut PainterBot::new();
-    let mut cpu = Machine::new(program, bot);
-    cpu.run();
-
-    let white_tiles: HashSet<(i64, i64)> = bot
-        .grid()
-        .iter()
-        .filter_map(|(pos, color)| match color {
-            Color::White => Some(*pos),
-            _ => None,
-        })
-        .collect();
-
-    let (min_x, max_x) = match white_tiles.iter().minmax_by_key(|(x, _)| x) {
-        MinMax(&(min_x, _), &(max_x, _)) => (min_x, max_x),
-        _ => panic!("Incorrect picture"),
+    let input_arg = Arg::with_name("input")
+        .value_name("FILE")
+        .help("Input file (defaults to STDIN if not provided)");
+    let output_arg = Arg::with_name("output")
+        .long("output")
+        .value_name("IMAGE")
+        .help("Also saves the identifier as an image; the extension picks PNG, PPM or PBM");
+    let frames_arg = Arg::with_name("frames")
+        .long("frames")
+        .value_name("DIR")
+        .help("Saves the robot's progress as DIR/frame_NNNNN.png, one frame per painted panel");
+    let frame_step_arg = Arg::with_name("frame-step")
+        .long("frame-step")
+        .value_name("N")
+        .default_value("1")
+        .help("Panels painted between consecutive frames");
+    let scale_arg = Arg::with_name("scale")
+        .long("scale")
+        .value_name("PIXELS")
+        .default_value("10")
+        .help("Side length of a panel in the images");
+    let color_args = [
+        ("white", "#ffffff", "Color of white panels"),
+        ("black", "#000000", "Color of black and unpainted panels"),
+        ("robot", "#ff0000", "Color of the robot in the frames"),
+    ]
+    .map(|(name, default, help)| {
+        Arg::with_name(name)
+            .long(name)
+            .value_name("#RRGGBB")
+            .default_value(default)
+            .help(help)
+    });
+    let matches = App::new("")
+        .about("Day 11: Space Police - Part 2")
+        .args(&[input_arg, output_arg, frames_arg, frame_step_arg, scale_arg])
+        .args(&color_args)
+        .get_matches();
+
+    let program = match matches.value_of("input") {
+        Some(path) => read_intcode_program(BufReader::new(File::open(path)?))?,
+        None => read_intcode_program(std::io::stdin().lock())?,
     };
-
-    let (min_y, max_y) = match white_tiles.iter().minmax_by_key(|(_, y)| y) {
-        MinMax(&(_, min_y), &(_, max_y)) => (min_y, max_y),
-        _ => panic!("Incorrect picture"),
+    let number = |name| -> std::io::Result<usize> {
+        match matches.value_of(name).unwrap().parse() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(std::io::Error::other(format!(
+                "--{} must be positive",
+                name
+            ))),
+        }
+    };
+    let color = |name| -> std::io::Result<Rgb> { Ok(matches.value_of(name).unwrap().parse()?) };
+    let options = ExportOptions {
+        image: matches.value_of("output").map(PathBuf::from),
+        frames: matches.value_of("frames").map(PathBuf::from),
+        frame_step: number("frame-step")?,
+        palette: Palette {
+            scale: number("scale")?,
+            black: color("black")?,
+            white: color("white")?,
+            robot: color("robot")?,
+        },
     };
 
-    let picture = (min_y..=max_y)
-        .rev()
-        .map(|y| {
-            (min_x..=max_x)
-                .map(|x| {
-                    if white_tiles.contains(&(x, y)) {
-                        '#'
-                    } else {
-                        ' '
-                    }
-                })
-                .collect::<String>()
-        })
-        .join("\n");
-
-    println!("{}", picture);
+    println!("{}", paint_and_export(program, &options)?);
 
     Ok(())
 }
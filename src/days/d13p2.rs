@@ -1,89 +1,62 @@
-use aoc::intcode::{IoProvider, Machine};
-use aoc::utils::parse_intcode_program;
-use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{BufReader, Error};
+use std::path::PathBuf;
+use std::time::Duration;
 
-enum InstructionState {
-    X,
-    Y,
-    Id,
-}
-
-struct Arcade {
-    current_x: i64,
-    current_y: i64,
-    paddle_x: i64,
-    ball_x: i64,
-    score: i64,
-    state: InstructionState,
-}
-
-impl InstructionState {
-    pub fn advance(&mut self) {
-        *self = match self {
-            InstructionState::X => InstructionState::Y,
-            InstructionState::Y => InstructionState::Id,
-            InstructionState::Id => InstructionState::X,
-        };
-    }
-}
+use clap::{App, Arg};
 
-impl Arcade {
-    pub fn new() -> Self {
-        Arcade {
-            current_x: 0,
-            current_y: 0,
-            paddle_x: 0,
-            ball_x: 0,
-            score: 0,
-            state: InstructionState::X,
-        }
-    }
-
-    pub fn score(&self) -> i64 {
-        self.score
-    }
-
-    fn process_tile(&mut self, x: i64, id: i64) {
-        match id {
-            3 => self.paddle_x = x,
-            4 => self.ball_x = x,
-            _ => (),
-        }
-    }
-}
-
-impl IoProvider for Arcade {
-    fn send_input(&mut self) -> i64 {
-        match Ord::cmp(&self.paddle_x, &self.ball_x) {
-            Ordering::Greater => -1,
-            Ordering::Less => 1,
-            Ordering::Equal => 0,
-        }
-    }
-
-    fn get_output(&mut self, value: i64) {
-        match self.state {
-            InstructionState::X => self.current_x = value,
-            InstructionState::Y => self.current_y = value,
-            InstructionState::Id => match (self.current_x, self.current_y) {
-                (-1, 0) => self.score = value,
-                (x, _) => self.process_tile(x, value),
-            },
-        }
-
-        self.state.advance();
-    }
-}
+use aoc::days::d13::{play, Control, PlayOptions};
+use aoc::utils::read_intcode_program;
 
 fn main() -> std::io::Result<()> {
-    let program = parse_intcode_program("Day 13: Care Package - Part 2")?;
-
-    let arcade = &mut Arcade::new();
-    let mut cpu = Machine::new(program, arcade);
-    cpu.write(0, 2);
-    cpu.run();
-
-    println!("{}", arcade.score());
+    let input_arg = Arg::with_name("input")
+        .value_name("FILE")
+        .help("Input file (defaults to STDIN if not provided)");
+    let headless_arg = Arg::with_name("headless")
+        .long("headless")
+        .help("Only prints the final score instead of drawing the game");
+    let play_arg = Arg::with_name("play")
+        .long("play")
+        .help("Reads joystick moves from STDIN, one per line: a/h for left, d/l for right");
+    let dump_arg = Arg::with_name("dump-frames")
+        .long("dump-frames")
+        .value_name("DIR")
+        .help("Writes every frame as text to DIR/frame_NNNNN.txt");
+    let delay_arg = Arg::with_name("delay")
+        .long("delay")
+        .value_name("MS")
+        .default_value("20")
+        .help("Pause after drawing each frame");
+    let matches = App::new("")
+        .about("Day 13: Care Package - Part 2")
+        .args(&[input_arg, headless_arg, play_arg, dump_arg, delay_arg])
+        .get_matches();
+
+    let program = match matches.value_of("input") {
+        Some(path) => read_intcode_program(BufReader::new(File::open(path)?))?,
+        None if matches.is_present("play") => {
+            return Err(Error::other(
+                "--play reads moves from STDIN, give the program as a file",
+            ));
+        }
+        None => read_intcode_program(std::io::stdin().lock())?,
+    };
+    let options = PlayOptions {
+        control: match matches.is_present("play") {
+            true => Control::Keyboard,
+            false => Control::Auto,
+        },
+        draw: !matches.is_present("headless"),
+        delay: matches
+            .value_of("delay")
+            .unwrap()
+            .parse()
+            .map(Duration::from_millis)
+            .map_err(Error::other)?,
+        dump_frames: matches.value_of("dump-frames").map(PathBuf::from),
+    };
+
+    println!("{}", play(program, &options)?);
 
     Ok(())
 }
@@ -0,0 +1,3 @@
+fn main() -> std::io::Result<()> {
+    aoc::days::run_standalone(21, 2)
+}
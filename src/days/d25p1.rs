@@ -0,0 +1,12 @@
+use aoc::intcode::AsciiConsole;
+use aoc::utils::parse_intcode_program;
+
+// The droid is steered by hand: give the program as a file and type commands on STDIN.
+fn main() -> std::io::Result<()> {
+    let program = parse_intcode_program("Day 25: Cryostasis - Part 1")?;
+
+    let mut droid = AsciiConsole::new(program);
+    droid.interact(std::io::stdin().lock(), std::io::stdout().lock())?;
+
+    Ok(())
+}
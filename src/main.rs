@@ -0,0 +1,135 @@
+use std::fs::File;
+use std::io::{BufReader, Error, ErrorKind, Read};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+
+use aoc::days;
+use aoc::solution::{Answer, Solution};
+
+fn timed_solve(solution: &dyn Solution, input: &str) -> (std::io::Result<Answer>, Duration) {
+    let start = Instant::now();
+    let answer = solution.solve(input);
+
+    (answer, start.elapsed())
+}
+
+fn parse_number(matches: &ArgMatches, name: &str) -> std::io::Result<u32> {
+    let value = matches.value_of(name).unwrap();
+
+    value
+        .parse()
+        .map_err(|_| Error::other(format!("{} must be a number, got `{}`", name, value)))
+}
+
+/// Runs one solution, printing the answer to STDOUT and the time it took to STDERR.
+fn run_one(matches: &ArgMatches) -> std::io::Result<()> {
+    let (day, part) = (
+        parse_number(matches, "DAY")?,
+        parse_number(matches, "PART")?,
+    );
+    let solution = days::find(day, part)
+        .ok_or_else(|| Error::other(format!("No solution for day {} part {}", day, part)))?;
+
+    let mut input = String::new();
+    match matches.value_of("INPUT") {
+        Some(path) => BufReader::new(File::open(path)?).read_to_string(&mut input)?,
+        None => std::io::stdin().lock().read_to_string(&mut input)?,
+    };
+
+    let (answer, elapsed) = timed_solve(solution, &input);
+    println!("{}", answer?);
+    eprintln!("{} solved in {:.3?}", solution.description(), elapsed);
+
+    Ok(())
+}
+
+/// Runs every solution with an input file named `dayNN.txt` in `input_dir`,
+/// counting inputs that exist but cannot be read as failures.
+fn run_all(input_dir: &Path) -> std::io::Result<()> {
+    let mut total = Duration::ZERO;
+    let mut n_failed = 0;
+
+    for solution in days::solutions() {
+        let heading = format!("Day {:>2} Part {}", solution.day(), solution.part());
+        let path = input_dir.join(format!("day{:02}.txt", solution.day()));
+        let input = match std::fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                println!("{}  skipped, no {}", heading, path.display());
+                continue;
+            }
+            Err(err) => {
+                n_failed += 1;
+                println!("{}  cannot read {}: {}", heading, path.display(), err);
+                continue;
+            }
+        };
+
+        let (answer, elapsed) = timed_solve(solution, &input);
+        total += elapsed;
+        match answer {
+            Ok(Answer::Text(text)) if text.contains('\n') => {
+                println!("{}  {:>12.3?}\n{}", heading, elapsed, text)
+            }
+            Ok(answer) => println!("{}  {:>12.3?}  {}", heading, elapsed, answer),
+            Err(err) => {
+                n_failed += 1;
+                println!("{}  {:>12.3?}  error: {}", heading, elapsed, err);
+            }
+        }
+    }
+
+    println!("Total {:>17.3?}", total);
+
+    match n_failed {
+        0 => Ok(()),
+        n => Err(Error::other(format!("{} solutions failed", n))),
+    }
+}
+
+fn list() {
+    for solution in days::solutions() {
+        println!("{}", solution.description());
+    }
+}
+
+fn main() -> std::io::Result<()> {
+    let run = SubCommand::with_name("run")
+        .about("Runs a solution, or all of them, and reports how long it took")
+        .arg(
+            Arg::with_name("all")
+                .long("all")
+                .conflicts_with_all(&["DAY", "PART", "INPUT"])
+                .help("Runs every solution that has an input file"),
+        )
+        .arg(
+            Arg::with_name("inputs")
+                .long("inputs")
+                .value_name("DIR")
+                .default_value("input")
+                .help("Where --all looks for the inputs, named dayNN.txt"),
+        )
+        .arg(Arg::with_name("DAY").required_unless("all"))
+        .arg(Arg::with_name("PART").required_unless("all"))
+        .arg(Arg::with_name("INPUT").help("Input file (defaults to STDIN if not provided)"));
+    let list_command = SubCommand::with_name("list").about("Lists the available solutions");
+    let matches = App::new("aoc")
+        .about("Advent of Code 2019 solutions")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommands(vec![run, list_command])
+        .get_matches();
+
+    match matches.subcommand() {
+        ("run", Some(run)) if run.is_present("all") => {
+            run_all(Path::new(run.value_of("inputs").unwrap()))
+        }
+        ("run", Some(run)) => run_one(run),
+        ("list", _) => {
+            list();
+            Ok(())
+        }
+        _ => unreachable!("A subcommand is required"),
+    }
+}
@@ -0,0 +1,18 @@
+use std::io::Read;
+
+use itertools::Itertools;
+
+use aoc::intcode::assemble;
+use aoc::utils::BufferedInput;
+
+fn main() -> std::io::Result<()> {
+    let mut input = BufferedInput::parse_args("Intcode assembler")?;
+    let mut source = String::new();
+    input.read_to_string(&mut source)?;
+
+    let program = assemble(&source)?;
+
+    println!("{}", program.iter().join(","));
+
+    Ok(())
+}
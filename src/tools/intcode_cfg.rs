@@ -0,0 +1,10 @@
+use aoc::intcode::analyze;
+use aoc::utils::parse_intcode_program;
+
+fn main() -> std::io::Result<()> {
+    let program = parse_intcode_program("Intcode control-flow graph exporter")?;
+
+    print!("{}", analyze(&program).to_dot());
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use std::io::{BufRead, BufReader};
+
+use clap::{App, Arg};
+
+use aoc::intcode::Debugger;
+use aoc::utils::read_intcode_program;
+
+fn main() -> std::io::Result<()> {
+    let program_arg = Arg::with_name("program")
+        .value_name("FILE")
+        .required(true)
+        .help("Intcode program to debug");
+    let matches = App::new("")
+        .about("Intcode step debugger, reading commands from STDIN")
+        .arg(program_arg)
+        .get_matches();
+
+    let file = std::fs::File::open(matches.value_of("program").unwrap())?;
+    let program = read_intcode_program(BufReader::new(file))?;
+    let mut debugger = Debugger::new(program);
+
+    for line in std::io::stdin().lock().lines() {
+        match debugger.execute(&line?) {
+            Some(report) if report.is_empty() => (),
+            Some(report) => println!("{}", report.trim_end()),
+            None => break,
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use aoc::intcode::{disassemble, format_listing};
+use aoc::utils::parse_intcode_program;
+
+fn main() -> std::io::Result<()> {
+    let program = parse_intcode_program("Intcode disassembler")?;
+
+    let listing = format_listing(&disassemble(&program));
+
+    println!("{}", listing);
+
+    Ok(())
+}
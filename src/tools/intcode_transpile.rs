@@ -0,0 +1,10 @@
+use aoc::intcode::transpile;
+use aoc::utils::parse_intcode_program;
+
+fn main() -> std::io::Result<()> {
+    let program = parse_intcode_program("Intcode to Rust transpiler")?;
+
+    print!("{}", transpile(&program));
+
+    Ok(())
+}
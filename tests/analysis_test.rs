@@ -0,0 +1,99 @@
+use assert_cmd::Command;
+
+use aoc::intcode::{analyze, assemble, format_listing};
+
+// Day 5's comparison with 8.
+const COMPARE: [i64; 47] = [
+    3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+    1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20, 1105,
+    1, 46, 98, 99,
+];
+
+#[test]
+fn immediate_jumps_split_blocks() {
+    let graph = analyze(&COMPARE);
+
+    let edges: Vec<(usize, Vec<i64>)> = graph
+        .blocks()
+        .map(|b| (b.start, b.successors.clone()))
+        .collect();
+    assert_eq!(
+        edges,
+        vec![
+            (0, vec![22, 9]),
+            (9, vec![31, 16]),
+            (16, vec![36]),
+            (22, vec![46]),
+            (31, vec![46]),
+            (36, vec![46]),
+            (46, vec![]),
+        ]
+    );
+    assert_eq!(graph.block_containing(26).unwrap().start, 22);
+    assert!(graph.blocks().all(|b| !b.indirect));
+}
+
+#[test]
+fn unreached_words_are_data() {
+    let graph = analyze(&COMPARE);
+
+    assert!(graph.is_code(0));
+    assert!(graph.is_code(18));
+    assert!(!graph.is_code(19));
+    assert!(!graph.is_code(45));
+
+    let listing = format_listing(&graph.listing());
+    let lines: Vec<&str> = listing.lines().collect();
+    assert_eq!(lines[6..9], ["19: DATA 98", "20: DATA 0", "21: DATA 0"]);
+    assert_eq!(lines[17], "45: DATA 98");
+}
+
+#[test]
+fn indirect_and_invalid_jumps_are_marked() {
+    let program = assemble(
+        "
+              IN [t]
+              JNZ [t], rb+0
+              JEZ [t], #bad
+              JNZ [t], #-3
+              HLT
+        bad:  DATA 42
+        t:    DATA 0
+        ",
+    )
+    .unwrap();
+    let graph = analyze(&program);
+
+    let first = graph.block(0).unwrap();
+    assert!(first.indirect);
+    assert_eq!(first.successors, vec![5]);
+    assert_eq!(graph.block(5).unwrap().successors, vec![12, 8]);
+    assert_eq!(graph.block(8).unwrap().successors, vec![-3, 11]);
+    assert_eq!(graph.invalid_targets().collect::<Vec<_>>(), vec![-3, 12]);
+
+    let dot = graph.to_dot();
+    assert!(dot.contains("    bn3 [label=\"-3: negative address\", color=red, shape=octagon];\n"));
+    assert!(dot.contains("    b8 -> bn3;\n"));
+}
+
+#[test]
+fn cfg_tool_prints_dot() {
+    let expected = r#"digraph intcode {
+    node [shape=box, fontname="monospace"];
+    b0 [label="0: IN [10]\l2: JNZ [10], [11]\l"];
+    b5 [label="5: JEZ #0, #9\l"];
+    b9 [label="9: DATA 42", color=red, shape=octagon];
+    indirect [label="?", shape=diamond];
+    b0 -> b5 [style=dashed];
+    b0 -> indirect [style=dotted];
+    b5 -> b9;
+}
+"#;
+
+    let mut command = Command::cargo_bin("intcode-cfg").unwrap();
+    let assert = command
+        .write_stdin("3,10,5,10,11,1106,0,9,99,42,0,0\n")
+        .assert();
+
+    assert.success().stdout(expected);
+}
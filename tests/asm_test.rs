@@ -0,0 +1,140 @@
+use assert_cmd::Command;
+
+use aoc::intcode::{
+    assemble, disassemble, format_listing, AsmError, AsmErrorKind, Machine, QueueProvider,
+};
+
+#[test]
+fn assembles_modes_labels_and_data() {
+    let source = "
+        ; doubles its input
+        start: IN [value]
+               MUL [value], #2, rb-1
+               OUT rb-1
+               HLT
+        value: DATA 0, start + 3
+    ";
+
+    let program = assemble(source).unwrap();
+
+    assert_eq!(program, vec![3, 9, 21002, 9, 2, -1, 204, -1, 99, 0, 3]);
+}
+
+#[test]
+fn aliases_and_macros_expand() {
+    let source = "
+        .alias counter rb+0
+        .macro countdown from
+            ADD #from, #0, counter
+        loop@: OUT counter
+            ADD counter, #-1, counter
+            JNZ counter, #loop@
+        .endm
+
+        ARB #100
+        countdown 3
+        countdown 2
+        HLT
+    ";
+
+    let program = assemble(source).unwrap();
+    let io = &mut QueueProvider::new();
+    let mut machine = Machine::new(program, io);
+    machine.run().unwrap();
+
+    assert_eq!(io.drain_outputs(), vec![3, 2, 1, 2, 1]);
+}
+
+#[test]
+fn listing_round_trips() {
+    let program = vec![
+        3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1, 28,
+        1005, 28, 6, 99, 0, 0, 5, 12345,
+    ];
+
+    let listing = format_listing(&disassemble(&program));
+    assert_eq!(assemble(&listing).unwrap(), program);
+
+    let bare_listing: Vec<String> = disassemble(&program)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(assemble(&bare_listing.join("\n")).unwrap(), program);
+}
+
+#[test]
+fn extreme_values_round_trip() {
+    let program = vec![1101, i64::MIN, i64::MAX, 7, 99, i64::MIN, i64::MAX, 0];
+
+    let listing = format_listing(&disassemble(&program));
+    assert_eq!(assemble(&listing).unwrap(), program);
+    assert_eq!(
+        assemble("DATA -9223372036854775807 - 1, --9223372036854775807").unwrap(),
+        vec![i64::MIN, i64::MAX]
+    );
+}
+
+#[test]
+fn errors_carry_line_numbers() {
+    let error = |source| assemble(source).unwrap_err();
+
+    assert_eq!(
+        error("HLT\nFOO #1"),
+        AsmError {
+            line: 2,
+            kind: AsmErrorKind::UnknownMnemonic("FOO".to_string())
+        }
+    );
+    assert_eq!(
+        error("JNZ #1, #nowhere").kind,
+        AsmErrorKind::UnknownLabel("nowhere".to_string())
+    );
+    assert_eq!(error("a: HLT\na: HLT").line, 2);
+    assert_eq!(
+        error("DATA 9223372036854775807 + 1").kind,
+        AsmErrorKind::InvalidOperand("9223372036854775807 + 1".to_string())
+    );
+    assert_eq!(
+        error("DATA -9223372036854775808 - 1").kind,
+        AsmErrorKind::InvalidOperand("-9223372036854775808 - 1".to_string())
+    );
+    assert_eq!(
+        error("ADD #1, #2, #3").kind,
+        AsmErrorKind::ImmediateDestination
+    );
+    assert_eq!(
+        error("OUT #1, #2").kind,
+        AsmErrorKind::WrongArgCount {
+            expected: 1,
+            found: 2
+        }
+    );
+    assert_eq!(
+        error("0: HLT\n2: HLT").kind,
+        AsmErrorKind::AddressMismatch {
+            expected: 1,
+            found: 2
+        }
+    );
+    assert_eq!(
+        error(".macro m\nHLT").kind,
+        AsmErrorKind::UnterminatedMacro("m".to_string())
+    );
+    assert_eq!(
+        error(".macro m\nm\n.endm\nm").kind,
+        AsmErrorKind::MacroRecursion("m".to_string())
+    );
+
+    let io_error: std::io::Error = error("FOO").into();
+    assert_eq!(io_error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn asm_binary_prints_program() {
+    let input = "IN [5]\nOUT [5]\nHLT\nDATA 0\n";
+
+    let mut command = Command::cargo_bin("intcode-asm").unwrap();
+    let assert = command.write_stdin(input).assert();
+
+    assert.success().stdout("3,5,4,5,99,0\n");
+}
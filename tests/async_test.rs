@@ -0,0 +1,79 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use aoc::intcode::{block_on, AsyncMachine, AsyncQueue, Executor, IntcodeError};
+
+// Outputs its input plus one, forever.
+const INCREMENT: [i64; 12] = [3, 11, 101, 1, 11, 11, 4, 11, 1105, 1, 0, 0];
+
+fn increment() -> Vec<i64> {
+    INCREMENT.to_vec()
+}
+
+#[test]
+fn machine_awaits_its_input_queue() {
+    let queue = AsyncQueue::new();
+    let mut machine = AsyncMachine::new(vec![3, 0, 4, 0, 3, 0, 4, 0, 99], queue.clone());
+
+    queue.push(5);
+    queue.push(6);
+
+    assert_eq!(block_on(machine.collect_outputs()), Ok(vec![5, 6]));
+    assert!(machine.machine().is_halted());
+}
+
+#[test]
+fn closed_queue_ends_the_output_stream() {
+    let queue = AsyncQueue::new();
+    let mut machine = AsyncMachine::new(increment(), queue.clone());
+
+    queue.push(1);
+    queue.push(2);
+    queue.close();
+
+    assert_eq!(block_on(machine.collect_outputs()), Ok(vec![2, 3]));
+    assert!(!machine.machine().is_halted());
+}
+
+#[test]
+fn faults_surface_from_the_future() {
+    let mut machine = AsyncMachine::new(vec![42], AsyncQueue::new());
+
+    assert!(matches!(
+        block_on(machine.next_output()),
+        Err(IntcodeError::BadOpcode { .. })
+    ));
+}
+
+#[test]
+fn executor_drives_a_long_chain_on_one_thread() {
+    let queues: Vec<AsyncQueue> = (0..=300).map(|_| AsyncQueue::new()).collect();
+    let result = Rc::new(RefCell::new(Vec::new()));
+    let mut executor = Executor::new();
+
+    for stage in queues.windows(2) {
+        let mut machine = AsyncMachine::new(increment(), stage[0].clone());
+        let next = stage[1].clone();
+
+        executor.spawn(async move {
+            while let Ok(Some(value)) = machine.next_output().await {
+                next.push(value);
+            }
+            next.close();
+        });
+    }
+
+    let last = queues[300].clone();
+    let sink = Rc::clone(&result);
+    executor.spawn(async move {
+        let mut reader = AsyncMachine::new(vec![3, 7, 4, 7, 1105, 1, 0, 0], last);
+        *sink.borrow_mut() = reader.collect_outputs().await.unwrap();
+    });
+
+    queues[0].push(0);
+    queues[0].push(1000);
+    queues[0].close();
+
+    assert_eq!(executor.run(), 0);
+    assert_eq!(*result.borrow(), vec![300, 1300]);
+}
@@ -0,0 +1,63 @@
+use std::sync::mpsc::channel;
+
+use aoc::intcode::{spawn, ChannelProvider, Machine, RunState};
+
+const FEEDBACK_AMPLIFIER: [i64; 29] = [
+    3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1, 28, 1005,
+    28, 6, 99, 0, 0, 5,
+];
+
+#[test]
+fn spawned_machine_returns_its_final_state() {
+    let (io, input, output) = ChannelProvider::connected();
+    let handle = spawn(Machine::with_provider(vec![3, 0, 4, 0, 99], io));
+
+    input.send(17).unwrap();
+    assert_eq!(output.recv(), Ok(17));
+
+    let (machine, result) = handle.join().unwrap();
+    assert_eq!(result, Ok(RunState::Halted));
+    assert_eq!(machine.last_output(), Some(17));
+}
+
+#[test]
+fn closed_input_channel_pauses_the_machine() {
+    let (io, input, _output) = ChannelProvider::connected();
+    drop(input);
+
+    let (machine, result) = spawn(Machine::with_provider(vec![3, 0, 99], io))
+        .join()
+        .unwrap();
+
+    assert_eq!(result, Ok(RunState::NeedsInput));
+    assert_eq!(machine.program_counter(), 0);
+}
+
+#[test]
+fn amplifiers_run_concurrently_in_a_feedback_loop() {
+    let (senders, receivers): (Vec<_>, Vec<_>) = (0..5).map(|_| channel()).unzip();
+
+    for (sender, phase) in senders.iter().zip([9, 8, 7, 6, 5]) {
+        sender.send(phase).unwrap();
+    }
+    senders[0].send(0).unwrap();
+
+    let handles: Vec<_> = receivers
+        .into_iter()
+        .zip(senders.iter().cycle().skip(1).cloned())
+        .map(|(inputs, outputs)| {
+            let io = ChannelProvider::new(inputs, outputs);
+            spawn(Machine::with_provider(FEEDBACK_AMPLIFIER.to_vec(), io))
+        })
+        .collect();
+
+    let finished: Vec<_> = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+
+    assert!(finished
+        .iter()
+        .all(|(_, result)| *result == Ok(RunState::Halted)));
+    assert_eq!(finished[4].0.last_output(), Some(139629729));
+}
@@ -0,0 +1,71 @@
+use std::io::Cursor;
+
+use aoc::intcode::{assemble, AsciiConsole, ConsoleOutput};
+
+// Asks a question, then outputs the length of the answer times 1000.
+const QUIZ: &str = "
+          ARB #msg
+    print: JEZ rb+0, #read
+          OUT rb+0
+          ARB #1
+          JNZ #1, #print
+    read: IN [c]
+          EQ [c], #10, [t]
+          JNZ [t], #done
+          ADD [n], #1, [n]
+          JNZ #1, #read
+    done: MUL [n], #1000, [n]
+          OUT [n]
+          HLT
+    c:    DATA 0
+    t:    DATA 0
+    n:    DATA 0
+    msg:  DATA 63, 10, 0
+";
+
+fn quiz() -> AsciiConsole {
+    AsciiConsole::new(assemble(QUIZ).unwrap())
+}
+
+#[test]
+fn text_and_values_are_separated() {
+    let mut console = quiz();
+
+    assert_eq!(
+        console.read_until_prompt(),
+        Ok(ConsoleOutput {
+            text: "?\n".to_string(),
+            values: vec![]
+        })
+    );
+    assert!(!console.is_halted());
+
+    console.send_line("hello");
+    let output = console.read_until_prompt().unwrap();
+
+    assert_eq!(output.text, "");
+    assert_eq!(output.values, vec![5000]);
+    assert!(console.is_halted());
+}
+
+#[test]
+fn script_lines_are_sent_before_running() {
+    let mut console = quiz();
+    let output = console.run_script(["four"]).unwrap();
+
+    assert_eq!(output.text, "?\n");
+    assert_eq!(output.values, vec![4000]);
+}
+
+#[test]
+fn interactive_mode_relays_lines() {
+    let mut console = quiz();
+    let mut screen = Vec::new();
+
+    let values = console
+        .interact(Cursor::new("abc\nignored\n"), &mut screen)
+        .unwrap();
+
+    assert_eq!(values, vec![3000]);
+    assert_eq!(String::from_utf8(screen).unwrap(), "?\n3000\n");
+}
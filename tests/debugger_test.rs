@@ -0,0 +1,123 @@
+use assert_cmd::Command;
+
+use aoc::intcode::Debugger;
+
+// Reads a number, then counts down from it, printing each value.
+const COUNTDOWN: [i64; 14] = [3, 13, 4, 13, 1001, 13, -1, 13, 1005, 13, 2, 99, 0, 0];
+
+fn run_script(debugger: &mut Debugger, script: &[&str]) -> Vec<String> {
+    script
+        .iter()
+        .map_while(|line| debugger.execute(line))
+        .collect()
+}
+
+#[test]
+fn steps_and_shows_registers() {
+    let mut debugger = Debugger::new(COUNTDOWN.to_vec());
+
+    let reports = run_script(&mut debugger, &["step", "input 2", "step 2", "regs"]);
+
+    assert_eq!(reports[0], "waiting for input\n0: IN [13]");
+    assert_eq!(reports[1], "");
+    assert_eq!(reports[2], "output: 2\n4: ADD [13], #-1, [13]");
+    assert_eq!(reports[3], "pc=4 rb=0 last_output=2 halted=false");
+}
+
+#[test]
+fn stops_on_breakpoints_and_watchpoints() {
+    let mut debugger = Debugger::new(COUNTDOWN.to_vec());
+
+    let reports = run_script(
+        &mut debugger,
+        &[
+            "input 3",
+            "break 8",
+            "continue",
+            "delete 8",
+            "break op OUT",
+            "continue",
+            "delete op OUT",
+            "watch 13",
+            "continue",
+            "unwatch 13",
+            "finish",
+        ],
+    );
+
+    assert_eq!(reports[2], "output: 3\nbreakpoint at 8\n8: JNZ [13], #2");
+    assert_eq!(reports[5], "breakpoint at 2\n2: OUT [13]");
+    assert_eq!(
+        reports[8],
+        "output: 2\nwatchpoint [13]: 2 -> 1\n8: JNZ [13], #2"
+    );
+    assert_eq!(reports[10], "output: 1\nhalted\n");
+}
+
+#[test]
+fn dumps_and_pokes_memory() {
+    let mut debugger = Debugger::new(COUNTDOWN.to_vec());
+
+    let reports = run_script(
+        &mut debugger,
+        &[
+            "poke 0 99",
+            "mem 0 9",
+            "disas 2",
+            "step",
+            "frobnicate",
+            "quit",
+            "regs",
+        ],
+    );
+
+    assert_eq!(reports[1], "0: 99 13 4 13 1001 13 -1 13\n8: 1005 13");
+    assert_eq!(reports[2], "0: HLT\n1: DATA 13");
+    assert_eq!(reports[3], "halted\n");
+    assert_eq!(reports[4], "error: unknown command `frobnicate`");
+    assert_eq!(reports.len(), 5);
+    assert!(debugger.machine().is_halted());
+}
+
+#[test]
+fn rejects_unbounded_requests() {
+    let mut debugger = Debugger::new(COUNTDOWN.to_vec());
+
+    let reports = run_script(
+        &mut debugger,
+        &[
+            "mem 9 0",
+            "mem 0 18446744073709551615",
+            "poke 18446744073709551615 1",
+            "disas 18446744073709551615",
+        ],
+    );
+
+    assert_eq!(reports[0], "error: END is before START");
+    assert_eq!(reports[1], "error: at most 4096 cells can be shown at once");
+    assert_eq!(
+        reports[2],
+        "error: address 18446744073709551615 is too far past the end of memory"
+    );
+    assert_eq!(reports[3].lines().count(), 1024);
+    assert_eq!(debugger.machine().memory_len(), COUNTDOWN.len());
+}
+
+#[test]
+fn dbg_binary_runs_script_from_stdin() {
+    let dir = std::env::temp_dir().join(format!("intcode-dbg-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let program = dir.join("program.txt");
+    std::fs::write(&program, "3,13,4,13,1001,13,-1,13,1005,13,2,99,0,0\n").unwrap();
+
+    let mut command = Command::cargo_bin("intcode-dbg").unwrap();
+    let assert = command
+        .arg(&program)
+        .write_stdin("input 1\nfinish\nregs\n")
+        .assert();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert
+        .success()
+        .stdout("output: 1\nhalted\npc=12 rb=0 last_output=1 halted=true\n");
+}
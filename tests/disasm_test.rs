@@ -0,0 +1,85 @@
+use assert_cmd::Command;
+
+use aoc::intcode::{disassemble, format_listing, Arg, Args, Instruction, Op};
+
+#[test]
+fn disassembles_all_addressing_modes() {
+    let program = vec![1002, 4, 3, 4, 21101, 5, -7, 3, 109, -2, 99];
+    let instructions = disassemble(&program);
+
+    assert_eq!(
+        instructions[0],
+        Instruction::Op {
+            address: 0,
+            op: Op::Mul,
+            args: Args::Three(Arg::Position(4), Arg::Immediate(3), Arg::Position(4)),
+        }
+    );
+
+    let lines: Vec<String> = instructions.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        lines,
+        vec!["MUL [4], #3, [4]", "ADD #5, #-7, rb+3", "ARB #-2", "HLT",]
+    );
+}
+
+#[test]
+fn undecodable_words_become_data() {
+    // bad opcode, write through immediate, non-canonical mode digits, truncated instruction
+    let program = vec![42, 1101, 1, 2, 3, 11199, 1, 0];
+    let lines: Vec<String> = disassemble(&program)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    assert_eq!(
+        lines,
+        vec![
+            "DATA 42",
+            "ADD #1, #2, [3]",
+            "DATA 11199",
+            "DATA 1",
+            "DATA 0"
+        ]
+    );
+}
+
+#[test]
+fn listing_is_address_aligned() {
+    let program = vec![3, 9, 4, 9, 1005, 9, 0, 99, 0, 0, 0, 0];
+    let listing = format_listing(&disassemble(&program));
+
+    let expected = [
+        " 0: IN [9]",
+        " 2: OUT [9]",
+        " 4: JNZ [9], #0",
+        " 7: HLT",
+        " 8: DATA 0",
+    ];
+    let actual: Vec<&str> = listing.lines().take(5).collect();
+
+    assert_eq!(actual, expected);
+    assert!(listing.ends_with("11: DATA 0"));
+}
+
+#[test]
+fn words_round_trip() {
+    let program = vec![1002, 4, 3, 4, 21101, 5, -7, 3, 203, 1, 99, 7];
+    let words: Vec<i64> = disassemble(&program)
+        .iter()
+        .flat_map(Instruction::words)
+        .collect();
+
+    assert_eq!(words, program);
+}
+
+#[test]
+fn disasm_binary_prints_listing() {
+    let input = "1,0,0,0,99";
+    let expected = "0: ADD [0], [0], [0]\n4: HLT\n";
+
+    let mut command = Command::cargo_bin("intcode-disasm").unwrap();
+    let assert = command.write_stdin(input).assert();
+
+    assert.success().stdout(expected);
+}
@@ -59,6 +59,111 @@ fn day_seven_part_two() {
     assert.success().stdout(expected_str);
 }
 
+// Paints its starting panel and the one east of it white, then paints one black and halts.
+const HULL_ROBOT: &str = "3,19,104,1,104,1,3,19,104,1,104,1,3,19,104,0,104,0,99,0";
+
+#[test]
+fn day_eleven_part_two() {
+    let expected_str = "##\n";
+
+    let mut command = Command::cargo_bin("d11p2").unwrap();
+    let assert = command.write_stdin(HULL_ROBOT).assert();
+
+    assert.success().stdout(expected_str);
+}
+
+#[test]
+fn day_eleven_part_two_images() {
+    let dir = std::env::temp_dir().join(format!("d11p2-images-{}", std::process::id()));
+    let image = dir.join("hull.ppm");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut command = Command::cargo_bin("d11p2").unwrap();
+    command
+        .args(["--scale", "2", "--white", "#ff0000", "--output"])
+        .arg(&image)
+        .arg("--frames")
+        .arg(&dir)
+        .write_stdin(HULL_ROBOT)
+        .assert()
+        .success();
+
+    let ppm = std::fs::read(&image).unwrap();
+    assert!(ppm.starts_with(b"P6\n4 2\n255\n"));
+    assert_eq!(ppm[11..14], [255, 0, 0]);
+
+    let n_frames = (1..)
+        .take_while(|n| dir.join(format!("frame_{:05}.png", n)).exists())
+        .count();
+    assert_eq!(n_frames, 4);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn day_thirteen_part_one_ignores_unknown_tiles() {
+    let input = "104,0,104,0,104,7,104,1,104,0,104,2,99";
+    let expected_str = "1\n";
+
+    let mut command = Command::cargo_bin("d13p1").unwrap();
+    let assert = command.write_stdin(input).assert();
+
+    assert.success().stdout(expected_str);
+}
+
+// Draws two walls, a block, the paddle and the ball, reads the joystick once,
+// then clears the block and scores the joystick position plus 41.
+const ARCADE: &str = "1,100,100,100,104,0,104,0,104,1,104,1,104,0,104,2,104,2,104,0,104,1,104,1,\
+                      104,2,104,3,104,2,104,1,104,4,104,-1,104,0,104,0,3,59,104,1,104,0,104,0,\
+                      1001,59,41,59,104,-1,104,0,4,59,99,0";
+
+#[test]
+fn day_thirteen_part_two() {
+    let expected_str = "42\n";
+
+    let mut command = Command::cargo_bin("d13p2").unwrap();
+    let assert = command.arg("--headless").write_stdin(ARCADE).assert();
+
+    assert.success().stdout(expected_str);
+}
+
+#[test]
+fn day_thirteen_part_two_keyboard() {
+    let path = std::env::temp_dir().join(format!("d13p2-program-{}", std::process::id()));
+    std::fs::write(&path, ARCADE).unwrap();
+    let expected_str = "40\n";
+
+    let mut command = Command::cargo_bin("d13p2").unwrap();
+    let assert = command
+        .args(["--headless", "--play"])
+        .arg(&path)
+        .write_stdin("a\n")
+        .assert();
+
+    assert.success().stdout(expected_str);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn day_thirteen_part_two_frame_dump() {
+    let dir = std::env::temp_dir().join(format!("d13p2-frames-{}", std::process::id()));
+
+    let mut command = Command::cargo_bin("d13p2").unwrap();
+    command
+        .arg("--headless")
+        .arg("--dump-frames")
+        .arg(&dir)
+        .write_stdin(ARCADE)
+        .assert()
+        .success();
+
+    let first = std::fs::read_to_string(dir.join("frame_00001.txt")).unwrap();
+    let last = std::fs::read_to_string(dir.join("frame_00002.txt")).unwrap();
+    assert_eq!(first, "Score: 0\n#*#\n  o\n - \n");
+    assert_eq!(last, "Score: 42\n# #\n  o\n - \n");
+    assert!(!dir.join("frame_00003.txt").exists());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
 #[test]
 fn day_sixteen_part_one() {
     let input = "80871224585914546619083218645595";
@@ -80,3 +185,27 @@ fn day_sixteen_part_two() {
 
     assert.success().stdout(expected_str);
 }
+
+#[test]
+fn day_seventeen_part_one() {
+    let view = "..#..........\n\
+                ..#..........\n\
+                #######...###\n\
+                #.#...#...#.#\n\
+                #############\n\
+                ..#...#...#..\n\
+                ..#####...^..\n";
+    let input = view
+        .bytes()
+        .flat_map(|c| [104, i64::from(c)])
+        .chain([99])
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let expected_str = "76\n";
+
+    let mut command = Command::cargo_bin("d17p1").unwrap();
+    let assert = command.write_stdin(input).assert();
+
+    assert.success().stdout(expected_str);
+}
@@ -0,0 +1,493 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use aoc::intcode::{
+    DenseMemory, IntcodeError, Limits, Machine, Memory, PagedMemory, QueueProvider, RunState,
+};
+
+// Differential tests: random well-formed programs are run by `Machine` in each of its
+// configurations and by the small reference interpreter below, and must end in the same state.
+// The programs freely overwrite their own code, so they also exercise whatever they decode into.
+
+const BUDGET: u64 = 2_000;
+const DATA_CELLS: usize = 16;
+/// `max_memory` of the dense machines, which would otherwise try to allocate
+/// up to whatever address a program with large values writes to.
+const DENSE_MEMORY: usize = 1 << 20;
+
+/// xorshift64*, so the generated cases are the same on every run without extra dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next() % (high - low) as u64) as i64
+    }
+
+    fn percent(&mut self, chance: usize) -> bool {
+        self.below(100) < chance
+    }
+}
+
+/// How often the generator picks each kind of operand, in percent.
+#[derive(Clone, Copy)]
+struct Profile {
+    relative: usize,
+    far: usize,
+    /// Immediate operands and data near the ends of the `i64` range.
+    large: usize,
+}
+
+const BALANCED: Profile = Profile {
+    relative: 20,
+    far: 2,
+    large: 0,
+};
+
+/// (opcode, number of parameters, weight)
+const OPS: [(i64, usize, usize); 10] = [
+    (1, 3, 15),
+    (2, 3, 8),
+    (3, 1, 8),
+    (4, 1, 15),
+    (5, 2, 10),
+    (6, 2, 10),
+    (7, 3, 8),
+    (8, 3, 8),
+    (9, 1, 10),
+    (99, 0, 3),
+];
+
+fn pick_op(rng: &mut Rng) -> (i64, usize) {
+    let total: usize = OPS.iter().map(|&(_, _, weight)| weight).sum();
+    let mut roll = rng.below(total);
+
+    for &(code, n_params, weight) in OPS.iter() {
+        if roll < weight {
+            return (code, n_params);
+        }
+        roll -= weight;
+    }
+
+    unreachable!()
+}
+
+fn generate(rng: &mut Rng, profile: Profile) -> Vec<i64> {
+    let mut ops: Vec<(i64, usize)> = (0..1 + rng.below(40)).map(|_| pick_op(rng)).collect();
+    ops.push((99, 0));
+
+    let starts: Vec<usize> = ops
+        .iter()
+        .scan(0, |address, &(_, n_params)| {
+            let start = *address;
+            *address += n_params + 1;
+            Some(start)
+        })
+        .collect();
+    let code_len = starts.last().unwrap() + 1;
+    let total = (code_len + DATA_CELLS) as i64;
+
+    let mut program = Vec::new();
+    for &(code, n_params) in ops.iter() {
+        let mut word = code;
+        let mut operands = Vec::new();
+
+        for param in 0..n_params {
+            let is_destination = matches!((code, param), (1 | 2 | 7 | 8, 2) | (3, 0));
+            let is_target = matches!((code, param), (5 | 6, 1));
+
+            let mode = if code == 9 || is_target {
+                [1, 1, 1, 0, 2][rng.below(5)]
+            } else if is_destination && !rng.percent(1) {
+                [0, 2][usize::from(rng.percent(profile.relative))]
+            } else if rng.percent(profile.relative) {
+                2
+            } else {
+                rng.below(2) as i64
+            };
+
+            let operand = match mode {
+                1 if rng.percent(profile.large) => large(rng),
+                1 if code == 9 => rng.range(-6, 12),
+                1 if is_target && rng.percent(90) => starts[rng.below(starts.len())] as i64,
+                1 => rng.range(-10, 20),
+                0 if rng.percent(profile.far) => rng.range(total, 5_000),
+                0 if rng.percent(2) => -1,
+                0 if rng.percent(80) => rng.range(code_len as i64, total),
+                0 => rng.range(0, total),
+                _ => rng.range(-4, total + 8),
+            };
+
+            word += mode * 10_i64.pow(param as u32 + 2);
+            operands.push(operand);
+        }
+
+        program.push(word);
+        program.extend(operands);
+    }
+
+    program.extend((0..DATA_CELLS).map(|_| match rng.percent(profile.large) {
+        true => large(rng),
+        false => rng.range(-5, 20),
+    }));
+    program
+}
+
+fn large(rng: &mut Rng) -> i64 {
+    match rng.below(4) {
+        0 => i64::MAX - rng.range(0, 4),
+        1 => i64::MIN + rng.range(0, 4),
+        2 => i64::MAX / 2 + rng.range(-2, 3),
+        _ => rng.range(-(1 << 32), 1 << 32),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stop {
+    Halted,
+    NeedsInput,
+    Fault,
+    Overflow,
+    Limit,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Observed {
+    stop: Stop,
+    outputs: Vec<i64>,
+    /// Nonzero cells by address.
+    memory: BTreeMap<usize, i64>,
+    memory_len: usize,
+    program_counter: usize,
+    relative_base: i64,
+    steps: u64,
+}
+
+/// A straightforward interpreter written directly from the puzzle descriptions.
+struct Reference {
+    memory: BTreeMap<usize, i64>,
+    memory_len: usize,
+    /// Writes at or past this address stop the program, as `max_memory` does for `DenseMemory`.
+    memory_limit: Option<usize>,
+    pc: usize,
+    rb: i64,
+    steps: u64,
+    outputs: Vec<i64>,
+}
+
+/// Why the reference stopped early.
+type Step = Result<(), Stop>;
+
+impl Reference {
+    fn new(program: Vec<i64>, memory_limit: Option<usize>) -> Self {
+        Reference {
+            memory_len: program.len(),
+            memory: program.into_iter().enumerate().collect(),
+            memory_limit,
+            pc: 0,
+            rb: 0,
+            steps: 0,
+            outputs: Vec::new(),
+        }
+    }
+
+    fn run(&mut self, inputs: &mut VecDeque<i64>) -> Stop {
+        loop {
+            match self.step(inputs) {
+                Ok(()) => continue,
+                Err(stop) => return stop,
+            }
+        }
+    }
+
+    fn read(&self, address: i64) -> Result<i64, Stop> {
+        let address = usize::try_from(address).map_err(|_| Stop::Fault)?;
+
+        Ok(self.memory.get(&address).copied().unwrap_or(0))
+    }
+
+    fn mode(&self, param: u32) -> i64 {
+        self.memory.get(&self.pc).copied().unwrap_or(0) / 10_i64.pow(param + 2) % 10
+    }
+
+    fn operand(&self, param: u32) -> i64 {
+        self.read((self.pc + param as usize + 1) as i64).unwrap()
+    }
+
+    fn value(&self, param: u32) -> Result<i64, Stop> {
+        let operand = self.operand(param);
+
+        match self.mode(param) {
+            0 => self.read(operand),
+            1 => Ok(operand),
+            _ => self.read(self.rb.checked_add(operand).ok_or(Stop::Overflow)?),
+        }
+    }
+
+    fn address(&self, param: u32) -> Result<usize, Stop> {
+        let address = match self.mode(param) {
+            0 => self.operand(param),
+            _ => self
+                .rb
+                .checked_add(self.operand(param))
+                .ok_or(Stop::Overflow)?,
+        };
+
+        let address = usize::try_from(address).map_err(|_| Stop::Fault)?;
+        match self.memory_limit {
+            Some(limit) if address >= limit => Err(Stop::Limit),
+            _ => Ok(address),
+        }
+    }
+
+    fn store(&mut self, address: usize, value: i64) {
+        self.memory_len = self.memory_len.max(address + 1);
+        self.memory.insert(address, value);
+    }
+
+    fn step(&mut self, inputs: &mut VecDeque<i64>) -> Step {
+        if self.steps >= BUDGET {
+            return Err(Stop::Limit);
+        }
+        let word = self.read(self.pc as i64)?;
+        let (code, n_params) = match word % 100 {
+            code @ (1 | 2 | 7 | 8) => (code, 3),
+            code @ (5 | 6) => (code, 2),
+            code @ (3 | 4 | 9) => (code, 1),
+            99 => (99, 0),
+            _ => return Err(Stop::Fault),
+        };
+        let destination = match code {
+            1 | 2 | 7 | 8 => Some(2),
+            3 => Some(0),
+            _ => None,
+        };
+        for param in 0..n_params as u32 {
+            let mode = self.mode(param);
+            if mode > 2 || (mode == 1 && destination == Some(param)) {
+                return Err(Stop::Fault);
+            }
+        }
+
+        let mut next = self.pc + n_params + 1;
+        match code {
+            1 | 2 | 7 | 8 => {
+                let (a, b) = (self.value(0)?, self.value(1)?);
+                let address = self.address(2)?;
+                let result = match code {
+                    1 => a.checked_add(b).ok_or(Stop::Overflow)?,
+                    2 => a.checked_mul(b).ok_or(Stop::Overflow)?,
+                    7 => i64::from(a < b),
+                    _ => i64::from(a == b),
+                };
+                self.store(address, result);
+            }
+            3 => {
+                let address = self.address(0)?;
+                let input = inputs.pop_front().ok_or(Stop::NeedsInput)?;
+                self.store(address, input);
+            }
+            4 => {
+                let value = self.value(0)?;
+                self.outputs.push(value);
+            }
+            5 | 6 => {
+                let (condition, target) = (self.value(0)?, self.value(1)?);
+                if (condition != 0) == (code == 5) {
+                    next = usize::try_from(target).map_err(|_| Stop::Fault)?;
+                }
+            }
+            9 => {
+                self.rb = self.rb.checked_add(self.value(0)?).ok_or(Stop::Overflow)?;
+            }
+            _ => {
+                self.pc = next;
+                self.steps += 1;
+                return Err(Stop::Halted);
+            }
+        }
+
+        self.pc = next;
+        self.steps += 1;
+        Ok(())
+    }
+
+    fn observed(self, stop: Stop) -> Observed {
+        Observed {
+            stop,
+            outputs: self.outputs,
+            memory: self.memory.into_iter().filter(|&(_, v)| v != 0).collect(),
+            memory_len: self.memory_len,
+            program_counter: self.pc,
+            relative_base: self.rb,
+            steps: self.steps,
+        }
+    }
+}
+
+/// Runs `machine` to completion, providing `inputs` one at a time whenever it blocks.
+fn observe<M: Memory>(
+    mut machine: Machine<QueueProvider, M>,
+    inputs: &[i64],
+    predecode: bool,
+    max_memory: Option<usize>,
+) -> Observed {
+    machine.predecode = predecode;
+    machine.limits = Limits {
+        max_instructions: Some(BUDGET),
+        max_memory,
+        ..Limits::default()
+    };
+
+    let mut inputs = inputs.iter();
+    let mut outputs = Vec::new();
+    let stop = loop {
+        let result = machine.run();
+        outputs.extend(machine.provider_mut().drain_outputs());
+
+        match result {
+            Ok(RunState::NeedsInput) => match inputs.next() {
+                Some(&input) => machine.provider_mut().push_input(input),
+                None => break Stop::NeedsInput,
+            },
+            Ok(RunState::Halted) => break Stop::Halted,
+            Ok(state) => panic!("run returned {:?}", state),
+            Err(e) if e.is_limit() => break Stop::Limit,
+            Err(IntcodeError::ArithmeticOverflow { .. }) => break Stop::Overflow,
+            Err(_) => break Stop::Fault,
+        }
+    };
+
+    Observed {
+        stop,
+        outputs,
+        memory: machine
+            .memory()
+            .segments()
+            .into_iter()
+            .flat_map(|(start, cells)| (start..).zip(cells.iter().copied()))
+            .filter(|&(_, v)| v != 0)
+            .collect(),
+        memory_len: machine.memory_len(),
+        program_counter: machine.program_counter(),
+        relative_base: machine.relative_base(),
+        steps: machine.instructions_executed(),
+    }
+}
+
+/// Checks `cases` random programs against the reference, returning how each one stopped.
+fn check(seed: u64, cases: usize, profile: Profile) -> Vec<Stop> {
+    let mut rng = Rng(seed);
+    let mut stops = Vec::new();
+
+    while stops.len() < cases {
+        let program = generate(&mut rng, profile);
+        let inputs: Vec<i64> = (0..rng.below(6)).map(|_| rng.range(-50, 50)).collect();
+
+        let reference = |memory_limit| {
+            let mut reference = Reference::new(program.clone(), memory_limit);
+            let stop = reference.run(&mut inputs.iter().copied().collect());
+
+            reference.observed(stop)
+        };
+        let (dense_expected, paged_expected) = (reference(Some(DENSE_MEMORY)), reference(None));
+
+        for predecode in [true, false] {
+            let dense = DenseMemory::from(program.clone());
+            let paged = PagedMemory::from(program.clone());
+
+            let observed = observe(
+                Machine::with_memory(dense, QueueProvider::new()),
+                &inputs,
+                predecode,
+                Some(DENSE_MEMORY),
+            );
+            assert_eq!(observed, dense_expected, "{:?}", program);
+            let observed = observe(
+                Machine::with_memory(paged, QueueProvider::new()),
+                &inputs,
+                predecode,
+                None,
+            );
+            assert_eq!(observed, paged_expected, "{:?}", program);
+        }
+
+        stops.push(dense_expected.stop);
+    }
+
+    stops
+}
+
+fn assert_covers(stops: &[Stop], expected: &[Stop]) {
+    for stop in expected {
+        assert!(stops.contains(stop), "no case ended with {:?}", stop);
+    }
+}
+
+#[test]
+fn random_programs_match_the_reference() {
+    let stops = check(0x5eed, 500, BALANCED);
+
+    assert_covers(
+        &stops,
+        &[Stop::Halted, Stop::NeedsInput, Stop::Fault, Stop::Limit],
+    );
+}
+
+#[test]
+fn relative_mode_programs_match_the_reference() {
+    let profile = Profile {
+        relative: 70,
+        ..BALANCED
+    };
+
+    check(0xba5e, 500, profile);
+}
+
+#[test]
+fn memory_growth_matches_the_reference() {
+    let profile = Profile {
+        far: 30,
+        ..BALANCED
+    };
+
+    check(0xfa4, 500, profile);
+}
+
+#[test]
+fn arithmetic_overflow_matches_the_reference() {
+    let profile = Profile {
+        large: 25,
+        ..BALANCED
+    };
+
+    let stops = check(0xb16, 500, profile);
+
+    assert_covers(&stops, &[Stop::Overflow, Stop::Halted]);
+}
+
+#[test]
+fn reference_agrees_on_known_programs() {
+    // Day 9's quine and day 5's comparison with 8.
+    let quine = vec![
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let compare = vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+
+    let mut reference = Reference::new(quine.clone(), None);
+    assert_eq!(reference.run(&mut VecDeque::new()), Stop::Halted);
+    assert_eq!(reference.outputs, quine);
+
+    let mut reference = Reference::new(compare, None);
+    assert_eq!(reference.run(&mut VecDeque::from([8])), Stop::Halted);
+    assert_eq!(reference.outputs, vec![1]);
+}
@@ -0,0 +1,85 @@
+use aoc::grid::{Bounds, DenseGrid, Direction, Point, RaggedLineError, SparseGrid};
+
+#[test]
+fn directions_turn_and_step() {
+    let mut facing = Direction::North;
+    let mut position = Point::ORIGIN;
+
+    for _ in 0..4 {
+        position = position.step(facing);
+        facing = facing.turn_right();
+    }
+    assert_eq!((position, facing), (Point::ORIGIN, Direction::North));
+
+    assert_eq!(Direction::East.turn_left(), Direction::North);
+    assert_eq!(Direction::South.reverse(), Direction::North);
+    assert_eq!(Point::new(2, 3).step(Direction::North), Point::new(2, 2));
+    assert_eq!(Direction::from_arrow('<'), Some(Direction::West));
+    assert_eq!(Direction::West.arrow(), '<');
+    assert_eq!(Point::new(1, -2).manhattan(Point::new(-3, 4)), 10);
+}
+
+#[test]
+fn neighbours_are_four_and_eight_connected() {
+    let center = Point::new(5, 5);
+
+    let four: Vec<Point> = center.neighbours().collect();
+    assert_eq!(four.len(), 4);
+    assert!(four.iter().all(|p| p.manhattan(center) == 1));
+
+    let eight: Vec<Point> = center.neighbours_diagonal().collect();
+    assert_eq!(eight.len(), 8);
+    assert!(four.iter().all(|p| eight.contains(p)));
+
+    let grid = DenseGrid::new(3, 2, 0);
+    assert_eq!(grid.neighbours(Point::ORIGIN).count(), 2);
+    assert_eq!(grid.neighbours_diagonal(Point::new(1, 0)).count(), 5);
+}
+
+#[test]
+fn sparse_grid_parses_and_renders_within_its_bounds() {
+    let grid = SparseGrid::parse("..#..\n.#.#.\n..#..", |c| (c == '#').then_some(()));
+
+    assert_eq!(grid.len(), 4);
+    assert_eq!(
+        grid.bounds(),
+        Some(Bounds::new(Point::new(1, 0), Point::new(3, 2)))
+    );
+    assert_eq!(
+        grid.render(|cell| cell.map_or(' ', |_| '#')),
+        " # \n# #\n # "
+    );
+
+    let empty: SparseGrid<()> = SparseGrid::new();
+    assert_eq!(empty.bounds(), None);
+    assert_eq!(empty.render(|_| '#'), "");
+}
+
+#[test]
+fn dense_grid_parses_rectangles_only() {
+    let mut grid = DenseGrid::parse("ab\ncd\nef", |c| c).unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (2, 3));
+    assert_eq!(grid.get(Point::new(1, 2)), Some(&'f'));
+    assert_eq!(grid.get(Point::new(2, 0)), None);
+    assert!(grid.set(Point::new(0, 1), 'x'));
+    assert!(!grid.set(Point::new(-1, 0), 'x'));
+    assert_eq!(grid.render(|&c| c), "ab\nxd\nef");
+    assert_eq!(
+        grid.iter().map(|(p, _)| p).collect::<Vec<_>>(),
+        Bounds::new(Point::ORIGIN, Point::new(1, 2))
+            .points()
+            .collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        DenseGrid::parse("abc\nde", |c| c),
+        Err(RaggedLineError {
+            line: 2,
+            expected: 3,
+            found: 2
+        })
+    );
+    let error: std::io::Error = DenseGrid::parse("abc\nde", |c| c).unwrap_err().into();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,90 @@
+use aoc::grid::{Bounds, Point};
+use aoc::image::{Image, ImageFormat, Rgb};
+
+fn encode(image: &Image, format: ImageFormat) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    image.write(&mut bytes, format).unwrap();
+
+    bytes
+}
+
+#[test]
+fn colors_parse_from_hex() {
+    assert_eq!("#ff8000".parse(), Ok(Rgb(255, 128, 0)));
+    assert_eq!("0a0B0c".parse(), Ok(Rgb(10, 11, 12)));
+    assert!("#fff".parse::<Rgb>().is_err());
+    assert!("#gg0000".parse::<Rgb>().is_err());
+
+    let error: std::io::Error = "red".parse::<Rgb>().unwrap_err().into();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn bounds_are_scaled_into_pixels() {
+    let bounds = Bounds::new(Point::new(-1, 5), Point::new(0, 5));
+    let image = Image::from_bounds(bounds, 3, |p| match p.x {
+        -1 => Rgb::WHITE,
+        _ => Rgb(1, 2, 3),
+    });
+
+    assert_eq!((image.width(), image.height()), (6, 3));
+    assert_eq!(image.get(2, 2), Some(Rgb::WHITE));
+    assert_eq!(image.get(3, 0), Some(Rgb(1, 2, 3)));
+    assert_eq!(image.get(6, 0), None);
+
+    let ppm = encode(&image, ImageFormat::Ppm);
+    assert!(ppm.starts_with(b"P6\n6 3\n255\n"));
+    assert_eq!(ppm.len(), 11 + 6 * 3 * 3);
+    assert_eq!(ppm[11..17], [255, 255, 255, 255, 255, 255]);
+    assert_eq!(ppm[20..23], [1, 2, 3]);
+}
+
+#[test]
+fn bitmaps_pack_dark_pixels_per_row() {
+    let mut image = Image::new(10, 2, Rgb::WHITE);
+    image.set(0, 0, Rgb::BLACK);
+    image.set(9, 0, Rgb(20, 20, 20));
+    image.set(7, 1, Rgb(0, 0, 255));
+
+    let pbm = encode(&image, ImageFormat::Pbm);
+
+    assert_eq!(pbm, b"P4\n10 2\n\x80\x40\x01\x00".to_vec());
+}
+
+#[test]
+fn png_holds_the_scanlines_uncompressed() {
+    let mut image = Image::new(2, 1, Rgb::BLACK);
+    image.set(1, 0, Rgb(10, 20, 30));
+
+    let png = encode(&image, ImageFormat::Png);
+
+    assert_eq!(
+        png[..8],
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']
+    );
+    assert_eq!(png[8..16], [0, 0, 0, 13, b'I', b'H', b'D', b'R']);
+    assert_eq!(png[16..29], [0, 0, 0, 2, 0, 0, 0, 1, 8, 2, 0, 0, 0]);
+    assert_eq!(png[33..41], [0, 0, 0, 18, b'I', b'D', b'A', b'T']);
+
+    let zlib = &png[41..59];
+    let scanline = [0, 0, 0, 0, 10, 20, 30];
+    assert_eq!(zlib[..2], [0x78, 0x01]);
+    assert_eq!(zlib[2..7], [1, 7, 0, !7, 0xff]);
+    assert_eq!(zlib[7..14], scanline);
+
+    assert_eq!(
+        png[png.len() - 12..],
+        [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+    );
+}
+
+#[test]
+fn format_follows_the_extension() {
+    let format = |path: &str| ImageFormat::from_path(path.as_ref());
+
+    assert_eq!(format("hull.PNG"), Some(ImageFormat::Png));
+    assert_eq!(format("out/hull.pbm"), Some(ImageFormat::Pbm));
+    assert_eq!(format("hull.ppm"), Some(ImageFormat::Ppm));
+    assert_eq!(format("hull.gif"), None);
+    assert_eq!(format("hull"), None);
+}
@@ -0,0 +1,187 @@
+use aoc::intcode::{IntcodeError, IoProvider, Machine, QueueProvider, RunState, ValueProvider};
+
+#[test]
+fn unknown_opcode_is_reported() {
+    let io = &mut ValueProvider::new(0);
+    let mut machine = Machine::new(vec![1, 0, 0, 0, 42], io);
+
+    let err = machine.run().unwrap_err();
+
+    assert_eq!(
+        err,
+        IntcodeError::BadOpcode {
+            program_counter: 4,
+            instruction: 42
+        }
+    );
+    assert!(!machine.is_halted());
+}
+
+#[test]
+fn unknown_mode_is_reported() {
+    let io = &mut ValueProvider::new(0);
+    let mut machine = Machine::new(vec![301, 0, 0, 0, 99], io);
+
+    let err = machine.run().unwrap_err();
+
+    assert_eq!(
+        err,
+        IntcodeError::BadMode {
+            program_counter: 0,
+            instruction: 301,
+            mode: 3
+        }
+    );
+}
+
+#[test]
+fn negative_address_is_reported() {
+    let io = &mut ValueProvider::new(0);
+    let mut machine = Machine::new(vec![1, -1, 0, 0, 99], io);
+
+    let err = machine.run().unwrap_err();
+
+    assert_eq!(
+        err,
+        IntcodeError::NegativeAddress {
+            program_counter: 0,
+            instruction: 1,
+            address: -1
+        }
+    );
+}
+
+#[test]
+fn immediate_write_is_reported() {
+    let io = &mut ValueProvider::new(0);
+    let mut machine = Machine::new(vec![11101, 1, 1, 0, 99], io);
+
+    let err = machine.run().unwrap_err();
+
+    assert_eq!(err.program_counter(), 0);
+    assert_eq!(err.instruction(), 11101);
+    assert!(matches!(err, IntcodeError::ImmediateWrite { .. }));
+}
+
+#[test]
+fn machine_can_recover_after_fault() {
+    let io = &mut ValueProvider::new(0);
+    let mut machine = Machine::new(vec![1, 0, 0, 0, 42], io);
+
+    assert!(machine.run().is_err());
+
+    machine.write(4, 99).unwrap();
+    machine.run().unwrap();
+
+    assert!(machine.is_halted());
+    assert_eq!(machine.read(0), 2);
+}
+
+struct Inputs(Vec<i64>);
+
+impl IoProvider for Inputs {
+    fn send_input(&mut self) -> i64 {
+        self.try_send_input().unwrap()
+    }
+
+    fn try_send_input(&mut self) -> Option<i64> {
+        self.0.pop()
+    }
+}
+
+#[test]
+fn machine_pauses_when_input_is_missing() {
+    let io = &mut Inputs(vec![]);
+    let mut machine = Machine::new(vec![3, 7, 4, 7, 4, 7, 99, 0], io);
+
+    assert_eq!(machine.run(), Ok(RunState::NeedsInput));
+    assert_eq!(machine.run(), Ok(RunState::NeedsInput));
+
+    machine.provider_mut().0.push(17);
+    machine.interrupt_on_output = true;
+
+    assert_eq!(machine.run_until_interrupt(), Ok(RunState::Output(17)));
+    assert_eq!(machine.step(), Ok(RunState::Output(17)));
+    assert_eq!(machine.step(), Ok(RunState::Halted));
+    assert_eq!(machine.step(), Ok(RunState::Halted));
+}
+
+#[test]
+fn step_reports_running_for_plain_instructions() {
+    let io = &mut ValueProvider::new(0);
+    let mut machine = Machine::new(vec![1101, 1, 2, 5, 99, 0], io);
+
+    assert_eq!(machine.step(), Ok(RunState::Running));
+    assert_eq!(machine.read(5), 3);
+    assert_eq!(machine.run(), Ok(RunState::Halted));
+}
+
+#[test]
+fn queue_provider_feeds_inputs_and_collects_outputs() {
+    let io = &mut QueueProvider::with_inputs([3]);
+    let program = vec![3, 11, 3, 12, 1, 11, 12, 13, 4, 13, 99, 0, 0, 0];
+    let mut machine = Machine::new(program, io);
+
+    assert_eq!(machine.run(), Ok(RunState::NeedsInput));
+
+    machine.provider_mut().push_inputs(vec![4]);
+
+    assert_eq!(machine.run(), Ok(RunState::Halted));
+    assert_eq!(io.pending_inputs(), 0);
+    assert_eq!(io.drain_outputs(), vec![7]);
+    assert_eq!(io.pop_output(), None);
+}
+
+#[test]
+fn queue_provider_ascii_helpers() {
+    let mut io = QueueProvider::new();
+    io.push_line("Hi");
+
+    for value in [72, 105, 10, 1000, 33] {
+        io.get_output(value);
+    }
+
+    assert_eq!(io.send_input(), 'H' as i64);
+    assert_eq!(io.try_send_input(), Some('i' as i64));
+    assert_eq!(io.try_send_input(), Some('\n' as i64));
+    assert_eq!(io.try_send_input(), None);
+
+    assert_eq!(io.drain_ascii(), "Hi\n");
+    assert_eq!(io.pop_output(), Some(1000));
+    assert_eq!(io.drain_ascii(), "!");
+}
+
+fn echo_machine(input: i64) -> Machine<QueueProvider> {
+    let io = QueueProvider::with_inputs([input]);
+
+    Machine::with_provider(vec![3, 5, 4, 5, 99, 0], io)
+}
+
+#[test]
+fn owned_machine_can_be_returned_and_taken_apart() {
+    let mut machines: Vec<_> = (1..=3).map(echo_machine).collect();
+
+    for machine in machines.iter_mut() {
+        assert_eq!(machine.run(), Ok(RunState::Halted));
+    }
+
+    let outputs: Vec<i64> = machines
+        .into_iter()
+        .flat_map(|m| m.into_provider().drain_outputs())
+        .collect();
+
+    assert_eq!(outputs, vec![1, 2, 3]);
+}
+
+#[test]
+fn owned_machine_can_move_across_threads() {
+    let machine = echo_machine(42);
+
+    let handle = std::thread::spawn(move || {
+        let mut machine = machine;
+        machine.run().map(|_| machine)
+    });
+    let mut io = handle.join().unwrap().unwrap().into_provider();
+
+    assert_eq!(io.pop_output(), Some(42));
+}
@@ -0,0 +1,135 @@
+use aoc::intcode::{IntcodeError, Limits, Machine, PagedMemory, QueueProvider, RunState};
+
+// Outputs 1 forever.
+const LOOP: [i64; 5] = [104, 1, 1105, 1, 0];
+
+#[test]
+fn instruction_limit_stops_an_infinite_loop() {
+    let mut machine = Machine::with_provider(LOOP.to_vec(), QueueProvider::new());
+    machine.limits.max_instructions = Some(9);
+
+    let err = machine.run().unwrap_err();
+
+    assert_eq!(
+        err,
+        IntcodeError::InstructionLimit {
+            program_counter: 2,
+            instruction: 1105,
+            limit: 9
+        }
+    );
+    assert!(err.is_limit());
+    assert_eq!(machine.instructions_executed(), 9);
+    assert_eq!(machine.outputs_produced(), 5);
+}
+
+#[test]
+fn output_limit_fails_before_emitting() {
+    let mut machine = Machine::with_provider(LOOP.to_vec(), QueueProvider::new());
+    machine.limits = Limits {
+        max_outputs: Some(3),
+        ..Default::default()
+    };
+
+    let err = machine.run().unwrap_err();
+
+    assert!(matches!(err, IntcodeError::OutputLimit { limit: 3, .. }));
+    assert_eq!(machine.provider().outputs().count(), 3);
+    assert_eq!(machine.program_counter(), 0);
+}
+
+#[test]
+fn memory_limit_rejects_far_writes() {
+    let program = vec![3, 1_000_000, 99];
+    let mut machine = Machine::with_provider(program.clone(), QueueProvider::with_inputs([7]));
+    machine.limits.max_memory = Some(4096);
+
+    assert_eq!(
+        machine.run(),
+        Err(IntcodeError::MemoryLimit {
+            program_counter: 0,
+            instruction: 3,
+            address: 1_000_000,
+            limit: 4096
+        })
+    );
+    assert_eq!(machine.memory_len(), 3);
+    assert_eq!(machine.provider().pending_inputs(), 1);
+
+    let mut paged =
+        Machine::with_memory(PagedMemory::from(program), QueueProvider::with_inputs([7]));
+    paged.limits.max_memory = Some(4096);
+
+    assert_eq!(paged.run(), Ok(RunState::Halted));
+    assert_eq!(paged.read(1_000_000), 7);
+}
+
+#[test]
+fn direct_writes_are_checked() {
+    let mut machine = Machine::with_provider(vec![99], QueueProvider::new());
+
+    assert_eq!(
+        machine.write(usize::MAX, 1),
+        Err(IntcodeError::AddressOutOfRange {
+            program_counter: 0,
+            instruction: 99,
+            address: usize::MAX
+        })
+    );
+
+    machine.limits.max_memory = Some(4096);
+    assert_eq!(
+        machine.write(1_000_000, 1),
+        Err(IntcodeError::MemoryLimit {
+            program_counter: 0,
+            instruction: 99,
+            address: 1_000_000,
+            limit: 4096
+        })
+    );
+    assert_eq!(machine.write(10, 1), Ok(()));
+    assert_eq!(machine.memory_len(), 11);
+}
+
+#[test]
+fn machine_resumes_after_raising_a_limit() {
+    let mut machine = Machine::with_provider(LOOP.to_vec(), QueueProvider::new());
+    machine.limits.max_instructions = Some(4);
+    assert!(machine.run().is_err());
+
+    machine.limits.max_instructions = Some(8);
+    assert!(machine.run().is_err());
+
+    assert_eq!(machine.instructions_executed(), 8);
+    assert_eq!(machine.provider().outputs().count(), 4);
+}
+
+#[test]
+fn arithmetic_overflow_is_an_error() {
+    let cases = [
+        (vec![1101, i64::MAX, 1, 0, 99], 0, 1101),
+        (vec![1102, i64::MAX, 2, 0, 99], 0, 1102),
+        (vec![109, i64::MAX, 109, 1, 99], 2, 109),
+        (vec![109, i64::MIN, 204, -1, 99], 2, 204),
+    ];
+
+    for (program, program_counter, instruction) in cases {
+        let mut machine = Machine::with_provider(program, QueueProvider::new());
+        machine.limits = Limits {
+            max_instructions: Some(100),
+            max_memory: Some(4096),
+            max_outputs: None,
+        };
+
+        let err = machine.run().unwrap_err();
+
+        assert_eq!(
+            err,
+            IntcodeError::ArithmeticOverflow {
+                program_counter,
+                instruction
+            }
+        );
+        assert!(!err.is_limit());
+    }
+}
@@ -0,0 +1,81 @@
+use aoc::intcode::{
+    DenseMemory, IntcodeError, Machine, Memory, PagedMemory, QueueProvider, RunState, Snapshot,
+    PAGE_SIZE,
+};
+
+// Stores the input at address 1_000_000, then outputs it doubled.
+const FAR_STORE: [i64; 9] = [
+    3, 1_000_000, 1002, 1_000_000, 2, 1_000_000, 4, 1_000_000, 99,
+];
+
+#[test]
+fn backends_read_unwritten_cells_as_zero() {
+    let mut dense = DenseMemory::from(vec![1, 2, 3]);
+    let mut paged = PagedMemory::from(vec![1, 2, 3]);
+
+    dense.write(10, 7);
+    paged.write(10, 7);
+
+    for address in [0, 2, 5, 10, 11, 5000] {
+        assert_eq!(
+            dense.read(address),
+            paged.read(address),
+            "address {}",
+            address
+        );
+    }
+    assert_eq!(dense.len(), 11);
+    assert_eq!(paged.len(), 11);
+}
+
+#[test]
+fn paged_memory_allocates_only_touched_pages() {
+    let mut machine = Machine::with_memory(
+        PagedMemory::from(FAR_STORE.to_vec()),
+        QueueProvider::with_inputs([21]),
+    );
+
+    assert_eq!(machine.run(), Ok(RunState::Halted));
+    assert_eq!(machine.last_output(), Some(42));
+    assert_eq!(machine.read(1_000_000), 42);
+    assert_eq!(machine.memory_len(), 1_000_001);
+    assert_eq!(machine.memory().allocated(), 2 * PAGE_SIZE);
+}
+
+#[test]
+fn negative_addresses_are_errors_on_both_backends() {
+    let program = vec![1, -1, 0, 0, 99];
+
+    let mut dense = Machine::with_provider(program.clone(), QueueProvider::new());
+    let mut paged = Machine::with_memory(PagedMemory::from(program), QueueProvider::new());
+
+    let expected = Err(IntcodeError::NegativeAddress {
+        program_counter: 0,
+        instruction: 1,
+        address: -1,
+    });
+    assert_eq!(dense.run(), expected);
+    assert_eq!(paged.run(), expected);
+}
+
+#[test]
+fn paged_snapshot_round_trips_through_text() {
+    let mut machine = Machine::with_memory(
+        PagedMemory::from(FAR_STORE.to_vec()),
+        QueueProvider::with_inputs([5]),
+    );
+    machine.run().unwrap();
+
+    let mut saved = Vec::new();
+    machine.snapshot().write_to(&mut saved).unwrap();
+    let loaded = Snapshot::read_from(&saved[..]).unwrap();
+
+    assert_eq!(loaded, machine.snapshot());
+    assert_eq!(loaded.read(1_000_000), 10);
+    assert_eq!(loaded.read(999_999), 0);
+
+    let mut restored = Machine::with_memory(PagedMemory::default(), QueueProvider::new());
+    restored.restore(&loaded);
+    assert_eq!(restored.read(1_000_000), 10);
+    assert!(restored.is_halted());
+}
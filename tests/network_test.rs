@@ -0,0 +1,62 @@
+use aoc::intcode::{assemble, HookAction, Nat, Network, Packet};
+
+// Forwards every packet to the next address, incrementing its x.
+const FORWARDER: &str = "
+          IN [addr]
+    loop: IN [x]
+          EQ [x], #-1, [tmp]
+          JNZ [tmp], #loop
+          IN [y]
+          ADD [addr], #1, [tmp]
+          ADD [x], #1, [x]
+          OUT [tmp]
+          OUT [x]
+          OUT [y]
+          JNZ #1, #loop
+    addr: DATA 0
+    x:    DATA 0
+    y:    DATA 0
+    tmp:  DATA 0
+";
+
+fn forwarder() -> Vec<i64> {
+    assemble(FORWARDER).unwrap()
+}
+
+fn packet(destination: i64, x: i64, y: i64) -> Packet {
+    Packet { destination, x, y }
+}
+
+#[test]
+fn packets_are_routed_to_a_hook() {
+    let mut network = Network::new(&forwarder(), 3);
+    network.add_hook(3, |p: Packet| HookAction::Stop(p.x * 1000 + p.y));
+
+    assert_eq!(network.send(packet(0, 0, 42)), None);
+    assert_eq!(network.run(), Ok(Some(3042)));
+}
+
+#[test]
+fn idle_network_without_hooks_stops() {
+    let mut network = Network::new(&forwarder(), 2);
+
+    assert_eq!(network.run(), Ok(None));
+    assert!(network.is_idle());
+}
+
+#[test]
+fn nat_wakes_the_network_until_it_repeats() {
+    let mut network = Network::new(&forwarder(), 2);
+    network.add_hook(2, Nat::new());
+    network.send(packet(0, 0, 7));
+
+    assert_eq!(network.run(), Ok(Some(7)));
+}
+
+#[test]
+fn packets_to_unknown_addresses_are_dropped() {
+    let mut network = Network::new(&forwarder(), 1);
+    network.send(packet(0, 0, 1));
+
+    assert_eq!(network.run(), Ok(None));
+}
@@ -0,0 +1,70 @@
+use aoc::grid::Point;
+use aoc::ocr::{recognize, recognize_text, OcrError};
+
+// Letters as printed by day 11 part 2, with a blank column between them.
+const PICTURE: &str = "\
+###  #  # #  # ###   ##  #  #  ##  #  #
+#  # # #  #  # #  # #  # # #  #  # # # 
+#  # ##   #  # #  # #    ##   #    ##  
+###  # #  #  # ###  # ## # #  # ## # # 
+# #  # #  #  # # #  #  # # #  #  # # # 
+#  # #  #  ##  #  #  ### #  #  ### #  #";
+
+#[test]
+fn reads_a_registration_identifier() {
+    assert_eq!(recognize_text(PICTURE), Ok("RKURGKGK".to_string()));
+}
+
+#[test]
+fn reads_every_known_letter() {
+    let picture = "\
+.##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####
+#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..
+#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####";
+
+    assert_eq!(
+        recognize_text(picture),
+        Ok("ABCEFGHIJKLOPRSUYZ".to_string())
+    );
+}
+
+#[test]
+fn position_in_the_plane_does_not_matter() {
+    let lit = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (3, 5),
+    ]
+    .map(|(x, y)| Point::new(x - 40, y + 7));
+
+    assert_eq!(recognize(lit), Ok("L".to_string()));
+}
+
+#[test]
+fn unreadable_pictures_are_errors() {
+    assert_eq!(recognize_text("...\n..."), Err(OcrError::NoLitCells));
+    assert_eq!(recognize_text("##"), Err(OcrError::WrongHeight(1)));
+    let io_error: std::io::Error = OcrError::NoLitCells.into();
+    assert_eq!(io_error.kind(), std::io::ErrorKind::InvalidData);
+
+    let mut picture: Vec<String> = PICTURE.lines().map(String::from).collect();
+    picture[0].replace_range(5..6, " ");
+    let error = recognize_text(&picture.join("\n")).unwrap_err();
+
+    assert_eq!(
+        error,
+        OcrError::UnknownGlyph {
+            column: 5,
+            glyph: "...#\n#.#.\n##..\n#.#.\n#.#.\n#..#".to_string(),
+        }
+    );
+}
@@ -0,0 +1,53 @@
+use aoc::intcode::{Machine, Pipeline, QueueProvider};
+
+const AMPLIFIER: [i64; 17] = [
+    3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+];
+
+const FEEDBACK_AMPLIFIER: [i64; 29] = [
+    3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1, 28, 1005,
+    28, 6, 99, 0, 0, 5,
+];
+
+// Outputs its input plus one.
+const INCREMENT: [i64; 10] = [3, 9, 101, 1, 9, 9, 4, 9, 99, 0];
+
+#[test]
+fn series_passes_values_down_the_chain() {
+    let mut pipeline = Pipeline::new(&AMPLIFIER, &[4, 3, 2, 1, 0]);
+
+    assert_eq!(pipeline.run(0), Ok(Some(43210)));
+    assert!(pipeline.stages().iter().all(|stage| stage.is_halted()));
+}
+
+#[test]
+fn feedback_loop_runs_until_the_last_stage_halts() {
+    let mut pipeline = Pipeline::new(&FEEDBACK_AMPLIFIER, &[9, 8, 7, 6, 5]);
+    pipeline.feedback = true;
+
+    assert_eq!(pipeline.run(0), Ok(Some(139629729)));
+}
+
+#[test]
+fn stages_can_run_different_programs() {
+    let stages = vec![
+        Machine::with_provider(INCREMENT.to_vec(), QueueProvider::new()),
+        Machine::with_provider(AMPLIFIER.to_vec(), QueueProvider::with_inputs([1])),
+    ];
+    let mut pipeline = Pipeline::from_stages(stages);
+
+    assert_eq!(pipeline.run(4), Ok(Some(51)));
+}
+
+#[test]
+fn stalled_feedback_loop_stops() {
+    let stages = vec![Machine::with_provider(
+        vec![3, 0, 3, 0, 99],
+        QueueProvider::new(),
+    )];
+    let mut pipeline = Pipeline::from_stages(stages);
+    pipeline.feedback = true;
+
+    assert_eq!(pipeline.run(1), Ok(None));
+    assert!(!pipeline.stages()[0].is_halted());
+}
@@ -0,0 +1,94 @@
+use aoc::intcode::{assemble, Machine, QueueProvider, TraceRecorder};
+
+// Runs an `OUT` twice, rewriting it into `HLT` in between.
+const REWRITE_EXECUTED: &str = "
+    top:  OUT #1
+          JNZ [done], #end
+          ADD #0, #99, [top]
+          ADD #0, #1, [done]
+          JNZ #1, #top
+    end:  OUT #2
+          HLT
+    done: DATA 0
+";
+
+fn run(program: Vec<i64>, predecode: bool) -> (Vec<i64>, u64) {
+    let mut machine = Machine::with_provider(program, QueueProvider::new());
+    machine.predecode = predecode;
+    machine.run().unwrap();
+
+    let steps = machine.instructions_executed();
+    (machine.into_provider().drain_outputs(), steps)
+}
+
+#[test]
+fn rewritten_instruction_is_decoded_again() {
+    let program = assemble(REWRITE_EXECUTED).unwrap();
+
+    let (outputs, steps) = run(program.clone(), true);
+
+    assert_eq!(outputs, vec![1]);
+    assert_eq!((outputs, steps), run(program, false));
+}
+
+#[test]
+fn rewritten_operand_is_decoded_again() {
+    // Loops over `OUT #n`, incrementing the immediate operand in place.
+    let program = assemble(
+        "
+        loop: OUT #0
+              ADD [loop + 1], #1, [loop + 1]
+              LT [loop + 1], #3, [t]
+              JNZ [t], #loop
+              HLT
+        t:    DATA 0
+        ",
+    )
+    .unwrap();
+
+    let (outputs, _) = run(program, true);
+
+    assert_eq!(outputs, vec![0, 1, 2]);
+}
+
+#[test]
+fn poke_and_restore_invalidate_the_cache() {
+    let program = vec![104, 1, 1105, 1, 0];
+    let mut machine = Machine::with_provider(program, QueueProvider::new());
+    machine.interrupt_on_output = true;
+    let start = machine.snapshot();
+
+    machine.run_until_interrupt().unwrap();
+    machine.step().unwrap();
+    machine.write(1, 7).unwrap();
+    machine.run_until_interrupt().unwrap();
+    assert_eq!(machine.last_output(), Some(7));
+
+    machine.write(0, 99).unwrap();
+    machine.run().unwrap();
+    assert!(machine.is_halted());
+
+    machine.restore(&start);
+    machine.run_until_interrupt().unwrap();
+    assert_eq!(machine.last_output(), Some(1));
+}
+
+#[test]
+fn traces_match_with_and_without_cache() {
+    let program = assemble(REWRITE_EXECUTED).unwrap();
+
+    let traces: Vec<_> = [false, true]
+        .into_iter()
+        .map(|predecode| {
+            let recorder = TraceRecorder::new();
+            let mut machine = Machine::with_provider(program.clone(), QueueProvider::new());
+            machine.predecode = predecode;
+            machine.set_tracer(recorder.clone());
+            machine.run().unwrap();
+
+            recorder.entries()
+        })
+        .collect();
+
+    assert_eq!(traces[0], traces[1]);
+}
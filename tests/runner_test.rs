@@ -0,0 +1,107 @@
+use std::io::ErrorKind;
+
+use assert_cmd::Command;
+
+use aoc::days;
+use aoc::solution::Answer;
+
+const AMPLIFIERS: &str = "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0";
+
+#[test]
+fn registry_is_ordered_and_complete() {
+    let keys: Vec<(u32, u32)> = days::solutions()
+        .map(|solution| (solution.day(), solution.part()))
+        .collect();
+
+    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(keys.len(), 27);
+    assert!(days::find(25, 1).is_none());
+
+    let solution = days::find(7, 1).unwrap();
+    assert_eq!(
+        solution.description(),
+        "Day 7: Amplification Circuit - Part 1"
+    );
+    assert_eq!(solution.solve(AMPLIFIERS).unwrap(), Answer::Number(43210));
+}
+
+#[test]
+fn malformed_inputs_are_invalid_data() {
+    let cases = [
+        (1, 1, "12\nheavy\n"),
+        (2, 1, "1,0,x,99"),
+        (4, 1, "100-200-300"),
+        (6, 1, "COM)B\nC\n"),
+        (6, 2, "COM)B\nB)YOU\n"),
+        (11, 1, "104,5,99"),
+        (11, 1, "104,1,104,2,99"),
+        (16, 1, "1234x678"),
+        (16, 2, "123456"),
+    ];
+
+    for (day, part, input) in cases {
+        let solution = days::find(day, part).unwrap();
+        let err = solution.solve(input).unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidData, "{:?}: {}", input, err);
+    }
+}
+
+#[test]
+fn runs_one_solution() {
+    let mut command = Command::cargo_bin("aoc").unwrap();
+    let assert = command
+        .args(["run", "7", "1"])
+        .write_stdin(AMPLIFIERS)
+        .assert();
+
+    let assert = assert.success().stdout("43210\n");
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr);
+    assert!(stderr.starts_with("Day 7: Amplification Circuit - Part 1 solved in "));
+}
+
+#[test]
+fn unknown_solutions_are_errors() {
+    let mut command = Command::cargo_bin("aoc").unwrap();
+
+    command
+        .args(["run", "25", "2"])
+        .write_stdin("")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn runs_every_solution_with_an_input() {
+    let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("day01.txt"), "12\n14\n1969\n100756\n").unwrap();
+    std::fs::write(dir.join("day07.txt"), AMPLIFIERS).unwrap();
+    std::fs::write(dir.join("day16.txt"), "").unwrap();
+    std::fs::create_dir_all(dir.join("day09.txt")).unwrap();
+
+    let mut command = Command::cargo_bin("aoc").unwrap();
+    let output = command
+        .args(["run", "--all", "--inputs"])
+        .arg(&dir)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // The other solutions still run after day 9 fails to read its input
+    // and day 16 fails on its empty input.
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines.len(), 28);
+    assert!(lines[0].starts_with("Day  1 Part 1") && lines[0].ends_with("  34241"));
+    assert!(lines[1].ends_with("  51316"));
+    assert!(lines[2].starts_with("Day  2 Part 1  skipped, no "));
+    assert!(lines[10].starts_with("Day  7 Part 1") && lines[10].ends_with("  43210"));
+    assert!(lines[12].starts_with("Day  9 Part 1  cannot read "));
+    assert!(lines[13].starts_with("Day  9 Part 2  cannot read "));
+    assert!(lines[19].ends_with("error: Input has no content"));
+    assert!(lines[20].contains("skipped"));
+    assert!(lines[27].starts_with("Total"));
+}
@@ -0,0 +1,150 @@
+use std::io::ErrorKind;
+
+use aoc::intcode::{Machine, QueueProvider, RunState, Snapshot};
+
+// Adds together the two inputs, with relative-mode storage past the end of the program.
+const ADDER: [i64; 11] = [109, 20, 203, 0, 203, 1, 22201, 0, 1, 2, 204];
+
+fn adder() -> Vec<i64> {
+    let mut program = ADDER.to_vec();
+    program.extend([2, 99]);
+
+    program
+}
+
+#[test]
+fn forks_a_machine_mid_run() {
+    let mut machine = Machine::with_provider(adder(), QueueProvider::with_inputs([10]));
+    assert_eq!(machine.run(), Ok(RunState::NeedsInput));
+
+    let prefix = machine.snapshot();
+    let results: Vec<i64> = (1..=3)
+        .map(|second| {
+            let io = QueueProvider::with_inputs([second]);
+            let mut fork = Machine::from_snapshot(&prefix, io);
+            fork.run().unwrap();
+
+            fork.last_output().unwrap()
+        })
+        .collect();
+
+    assert_eq!(results, vec![11, 12, 13]);
+    assert_eq!(machine.program_counter(), prefix.program_counter());
+}
+
+#[test]
+fn restore_rewinds_the_same_machine() {
+    let mut machine = Machine::with_provider(adder(), QueueProvider::with_inputs([1, 2]));
+    let start = machine.snapshot();
+
+    machine.run().unwrap();
+    assert!(machine.is_halted());
+    assert_eq!(machine.last_output(), Some(3));
+
+    machine.restore(&start);
+    machine.provider_mut().push_inputs([5, 6]);
+
+    assert!(!machine.is_halted());
+    assert_eq!(machine.last_output(), None);
+    assert_eq!(machine.run(), Ok(RunState::Halted));
+    assert_eq!(machine.last_output(), Some(11));
+}
+
+#[test]
+fn snapshot_survives_a_round_trip_through_text() {
+    let mut machine = Machine::with_provider(adder(), QueueProvider::with_inputs([4]));
+    machine.interrupt_on_output = true;
+    machine.run().unwrap();
+
+    let snapshot = machine.snapshot();
+    let mut saved = Vec::new();
+    snapshot.write_to(&mut saved).unwrap();
+
+    let text = String::from_utf8(saved.clone()).unwrap();
+    assert!(text.starts_with("intcode-snapshot 2\nprogram_counter 4\nrelative_base 20\n"));
+
+    let loaded = Snapshot::read_from(&saved[..]).unwrap();
+    assert_eq!(loaded, snapshot);
+
+    let mut resumed = Machine::from_snapshot(&loaded, QueueProvider::with_inputs([4]));
+    assert_eq!(resumed.run_until_interrupt(), Ok(RunState::Output(8)));
+}
+
+#[test]
+fn malformed_snapshots_are_rejected() {
+    let error = |text: &str| Snapshot::read_from(text.as_bytes()).unwrap_err().kind();
+
+    assert_eq!(error(""), ErrorKind::InvalidData);
+    assert_eq!(
+        error("intcode-snapshot 1\nmemory 1,2\n"),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(
+        error(
+            "intcode-snapshot 1\nprogram_counter x\nrelative_base 0\nhalted false\n\
+             interrupt_on_output false\nlast_output none\ninstructions_executed 0\nmemory 99\n"
+        ),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn loads_snapshots_without_an_output_count() {
+    let text = "intcode-snapshot 1\nprogram_counter 4\nrelative_base 20\nhalted false\n\
+                interrupt_on_output false\nlast_output none\ninstructions_executed 2\n\
+                memory 109,20,203,0,203,1,22201,0,1,2,204,2,99,0,0,0,0,0,0,0,10\n";
+
+    let snapshot = Snapshot::read_from(text.as_bytes()).unwrap();
+    let mut machine = Machine::from_snapshot(&snapshot, QueueProvider::with_inputs([5]));
+
+    assert_eq!(machine.run(), Ok(RunState::Halted));
+    assert_eq!(machine.last_output(), Some(15));
+    assert_eq!(machine.outputs_produced(), 1);
+}
+
+#[test]
+fn unknown_versions_are_rejected() {
+    let with_header = |header: &str| {
+        let text = format!(
+            "{}\nprogram_counter 0\nrelative_base 0\nhalted false\n\
+             interrupt_on_output false\nlast_output none\ninstructions_executed 0\nmemory 99\n",
+            header
+        );
+        Snapshot::read_from(text.as_bytes())
+    };
+
+    assert!(with_header("intcode-snapshot 1").is_ok());
+    for header in [
+        "intcode-snapshot 2",
+        "intcode-snapshot 3",
+        "intcode-snapshot 0",
+        "intcode-snapshot",
+        "intcode-snapshot x",
+    ] {
+        let error = with_header(header).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData, "{}", header);
+    }
+}
+
+#[test]
+fn far_memory_segments_are_rejected() {
+    let with_segment = |segment: &str| {
+        let text = format!(
+            "intcode-snapshot 1\nprogram_counter 0\nrelative_base 0\nhalted false\n\
+             interrupt_on_output false\nlast_output none\ninstructions_executed 0\n\
+             outputs_produced 0\nmemory 99\n{}\n",
+            segment
+        );
+        Snapshot::read_from(text.as_bytes())
+    };
+
+    assert!(with_segment("memory@1024 7").is_ok());
+    for segment in [
+        "memory@18446744073709551615 1,2",
+        "memory@67108864 1",
+        "memory@1000000000000 1",
+    ] {
+        let error = with_segment(segment).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+}
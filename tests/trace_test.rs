@@ -0,0 +1,127 @@
+use aoc::intcode::{
+    write_trace, Args, Machine, Op, QueueProvider, TraceFilter, TraceFormat, TraceRecorder,
+    TraceWriter,
+};
+
+// ARB #4, IN rb+7, MUL rb+7, #2, [11], OUT [11], HLT, <data>
+const DOUBLER: [i64; 12] = [109, 4, 203, 7, 1202, 7, 2, 11, 4, 11, 99, 0];
+
+fn traced_run(recorder: &TraceRecorder) -> Machine<QueueProvider> {
+    let mut machine = Machine::with_provider(DOUBLER.to_vec(), QueueProvider::with_inputs([21]));
+    machine.set_tracer(recorder.clone());
+    machine.run().unwrap();
+
+    machine
+}
+
+#[test]
+fn records_every_executed_instruction() {
+    let recorder = TraceRecorder::new();
+    traced_run(&recorder);
+
+    let entries = recorder.entries();
+    let ops: Vec<Op> = entries.iter().map(|e| e.op).collect();
+    assert_eq!(ops, vec![Op::Rel, Op::Inp, Op::Mul, Op::Out, Op::Hlt]);
+
+    assert_eq!(entries[0].relative_base, Some((0, 4)));
+    assert_eq!(entries[1].input, Some(21));
+    assert_eq!(entries[1].write, Some((11, 21)));
+    assert_eq!(entries[2].operands, vec![21, 2, 11]);
+    assert_eq!(entries[2].write, Some((11, 42)));
+    assert_eq!(entries[3].output, Some(42));
+    assert_eq!(entries[4].args, Args::Zero);
+    assert_eq!(entries[4].step, 4);
+}
+
+#[test]
+fn blocked_input_is_not_traced() {
+    let recorder = TraceRecorder::new();
+    let mut machine = Machine::with_provider(DOUBLER.to_vec(), QueueProvider::new());
+    machine.set_tracer(recorder.clone());
+
+    machine.run().unwrap();
+    machine.run().unwrap();
+    machine.provider_mut().push_input(1);
+    machine.run().unwrap();
+
+    let steps: Vec<(u64, usize)> = recorder
+        .entries()
+        .iter()
+        .map(|e| (e.step, e.program_counter))
+        .collect();
+    assert_eq!(steps, vec![(0, 0), (1, 2), (2, 4), (3, 8), (4, 10)]);
+}
+
+#[test]
+fn filters_by_address_and_op() {
+    let by_address = TraceRecorder::with_filter(TraceFilter {
+        addresses: Some(2..9),
+        ops: vec![],
+    });
+    traced_run(&by_address);
+
+    let by_op = TraceRecorder::with_filter(TraceFilter {
+        addresses: None,
+        ops: vec![Op::Out, Op::Hlt],
+    });
+    traced_run(&by_op);
+
+    let pcs = |r: &TraceRecorder| -> Vec<usize> {
+        r.entries().iter().map(|e| e.program_counter).collect()
+    };
+    assert_eq!(pcs(&by_address), vec![2, 4, 8]);
+    assert_eq!(pcs(&by_op), vec![8, 10]);
+}
+
+#[test]
+fn exports_json_lines_and_text() {
+    let recorder = TraceRecorder::new();
+    traced_run(&recorder);
+    let entries = recorder.entries();
+
+    let mut json = Vec::new();
+    write_trace(
+        &entries,
+        TraceFormat::JsonLines,
+        &Default::default(),
+        &mut json,
+    )
+    .unwrap();
+    let json = String::from_utf8(json).unwrap();
+    let lines: Vec<&str> = json.lines().collect();
+
+    assert_eq!(lines.len(), 5);
+    assert_eq!(
+        lines[2],
+        "{\"step\":2,\"pc\":4,\"op\":\"MUL\",\"args\":[\"rb+7\",\"#2\",\"[11]\"],\
+         \"operands\":[21,2,11],\"write\":{\"address\":11,\"value\":42},\"rb\":null,\
+         \"input\":null,\"output\":null}"
+    );
+
+    let text = entries[0].to_text();
+    assert!(text.starts_with("       0      0: ARB #4"));
+    assert!(text.ends_with("(4) rb 0 -> 4"));
+}
+
+#[test]
+fn streams_to_writer_and_hands_it_back() {
+    let mut machine = Machine::with_provider(DOUBLER.to_vec(), QueueProvider::with_inputs([1]));
+    let filter = TraceFilter {
+        addresses: None,
+        ops: vec![Op::Out],
+    };
+    machine.set_tracer(TraceWriter::with_filter(
+        Vec::new(),
+        TraceFormat::Text,
+        filter,
+    ));
+    machine.run().unwrap();
+
+    assert!(machine.take_tracer::<TraceRecorder>().is_none());
+
+    let writer = machine.take_tracer::<TraceWriter<Vec<u8>>>().unwrap();
+    let text = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+    assert_eq!(text.lines().count(), 1);
+    assert!(text.trim_end().ends_with("out 2"));
+}
@@ -0,0 +1,356 @@
+use std::path::Path;
+use std::process::Command;
+
+use aoc::intcode::{transpile, IntcodeError, Machine, QueueProvider, RunState};
+use aoc::utils::read_intcode_program;
+
+// The modules under `transpiled/` are the output of `transpile` for the programs below,
+// checked in so that the generated code is compiled and run as part of the tests.
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "transpiled/amplifier.rs"]
+mod amplifier;
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "transpiled/arcade.rs"]
+mod arcade;
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "transpiled/compare.rs"]
+mod compare;
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "transpiled/day_two.rs"]
+mod day_two;
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "transpiled/feedback.rs"]
+mod feedback;
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "transpiled/hull_robot.rs"]
+mod hull_robot;
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "transpiled/overflow.rs"]
+mod overflow;
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "transpiled/quine.rs"]
+mod quine;
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "transpiled/self_modifying.rs"]
+mod self_modifying;
+
+// Day 2's first example.
+const DAY_TWO: [i64; 9] = [1, 1, 1, 4, 99, 5, 6, 0, 99];
+
+// Day 7's amplifier, reading a phase setting and a signal.
+const AMPLIFIER: [i64; 17] = [
+    3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+];
+
+// Day 7's amplifier for feedback loops, reading a signal for each output until its count runs out.
+const FEEDBACK: [i64; 29] = [
+    3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1, 28, 1005,
+    28, 6, 99, 0, 0, 5,
+];
+
+// The day 11 and 13 programs from `example_test.rs`.
+const HULL_ROBOT: [i64; 20] = [
+    3, 19, 104, 1, 104, 1, 3, 19, 104, 1, 104, 1, 3, 19, 104, 0, 104, 0, 99, 0,
+];
+const ARCADE: [i64; 60] = [
+    1, 100, 100, 100, 104, 0, 104, 0, 104, 1, 104, 1, 104, 0, 104, 2, 104, 2, 104, 0, 104, 1, 104,
+    1, 104, 2, 104, 3, 104, 2, 104, 1, 104, 4, 104, -1, 104, 0, 104, 0, 3, 59, 104, 1, 104, 0, 104,
+    0, 1001, 59, 41, 59, 104, -1, 104, 0, 4, 59, 99, 0,
+];
+
+// Day 9's quine.
+const QUINE: [i64; 16] = [
+    109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+];
+
+// Day 5's comparison with 8.
+const COMPARE: [i64; 47] = [
+    3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+    1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20, 1105,
+    1, 46, 98, 99,
+];
+
+// Runs an `OUT` twice, rewriting it into `HLT` in between.
+const SELF_MODIFYING: [i64; 20] = [
+    104, 1, 1005, 19, 16, 1101, 0, 99, 0, 1101, 0, 1, 19, 1105, 1, 0, 104, 2, 99, 0,
+];
+
+// Adds, then multiplies past `i64::MAX`.
+const OVERFLOW: [i64; 10] = [1101, 1, 2, 9, 1102, i64::MAX, 2, 9, 99, 0];
+
+/// The parts of the generated `Program` that the differential tests compare.
+trait Compiled {
+    fn run(&mut self, io: &mut QueueProvider) -> Result<RunState, IntcodeError>;
+
+    fn read(&self, address: usize) -> i64;
+}
+
+macro_rules! impl_compiled {
+    ($($module:ident),*) => {
+        $(impl Compiled for $module::Program {
+            fn run(&mut self, io: &mut QueueProvider) -> Result<RunState, IntcodeError> {
+                $module::Program::run(self, io)
+            }
+
+            fn read(&self, address: usize) -> i64 {
+                $module::Program::read(self, address)
+            }
+        })*
+    };
+}
+
+impl_compiled!(amplifier, arcade, compare, day_two, feedback, hull_robot, quine);
+
+/// Runs `compiled` and the interpreter side by side, giving both the next batch of inputs
+/// whenever they stop, and checks that they stop, output and leave memory alike.
+fn assert_matches_interpreter<P: Compiled>(mut compiled: P, program: &[i64], inputs: &[&[i64]]) {
+    let mut machine = Machine::with_provider(program.to_vec(), QueueProvider::new());
+    let mut io = QueueProvider::new();
+
+    for batch in inputs.iter().copied().chain([&[][..]]) {
+        for &input in batch {
+            io.push_input(input);
+            machine.provider_mut().push_input(input);
+        }
+
+        let state = compiled.run(&mut io);
+        assert_eq!(state, machine.run(), "{:?} after {:?}", program, inputs);
+        assert_eq!(io.drain_outputs(), machine.provider_mut().drain_outputs());
+        if state != Ok(RunState::NeedsInput) {
+            break;
+        }
+    }
+
+    for address in 0..machine.memory_len() {
+        assert_eq!(
+            compiled.read(address),
+            machine.read(address),
+            "{:?}",
+            program
+        );
+    }
+}
+
+// Runs the transpiled `program.rs` with the inputs given as arguments.
+const PUZZLE_MAIN: &str = "#[path = \"program.rs\"]
+mod program;
+
+fn main() {
+    let inputs = std::env::args().skip(1).map(|arg| arg.parse().unwrap());
+    let mut io = aoc::intcode::QueueProvider::with_inputs(inputs.collect::<Vec<i64>>());
+    let state = program::Program::new().run(&mut io);
+    println!(\"{:?} {:?}\", state, io.drain_outputs());
+}
+";
+
+// Inputs to run each day's puzzle program with; programs that want more stop for input.
+const PUZZLE_RUNS: &[(u32, &[&[i64]])] = &[
+    (2, &[&[]]),
+    (5, &[&[1], &[5]]),
+    (7, &[&[0, 0], &[5, 0]]),
+    (9, &[&[1], &[2]]),
+    (11, &[&[0], &[1]]),
+    (13, &[&[]]),
+    (17, &[&[]]),
+    (19, &[&[0, 0], &[10, 20]]),
+    (21, &[&[]]),
+    (23, &[&[0, -1]]),
+    (25, &[&[]]),
+];
+
+/// Transpiles `program` and compiles it with `rustc` against the `aoc` library these tests use.
+fn compile_puzzle(program: &[i64], dir: &Path) -> std::path::PathBuf {
+    // Integration tests run from `target/<profile>/deps`, next to the library's dependencies.
+    let deps = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let library = deps.parent().unwrap().join("libaoc.rlib");
+    let binary = dir.join("program");
+
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join("program.rs"), transpile(program)).unwrap();
+    std::fs::write(dir.join("main.rs"), PUZZLE_MAIN).unwrap();
+
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let status = Command::new(rustc)
+        .args(["--edition", "2021", "-A", "warnings", "-o"])
+        .arg(&binary)
+        .arg(dir.join("main.rs"))
+        .arg("--extern")
+        .arg(format!("aoc={}", library.display()))
+        .arg("-L")
+        .arg(format!("dependency={}", deps.display()))
+        .status()
+        .unwrap();
+    assert!(status.success(), "transpiled program failed to compile");
+
+    binary
+}
+
+fn interpret(program: &[i64], inputs: &[i64]) -> Vec<i64> {
+    let mut machine = Machine::with_provider(
+        program.to_vec(),
+        QueueProvider::with_inputs(inputs.to_vec()),
+    );
+    machine.run().unwrap();
+
+    machine.into_provider().drain_outputs()
+}
+
+#[test]
+fn generated_sources_are_up_to_date() {
+    assert_eq!(transpile(&QUINE), include_str!("transpiled/quine.rs"));
+    assert_eq!(transpile(&COMPARE), include_str!("transpiled/compare.rs"));
+    assert_eq!(
+        transpile(&SELF_MODIFYING),
+        include_str!("transpiled/self_modifying.rs")
+    );
+    assert_eq!(transpile(&OVERFLOW), include_str!("transpiled/overflow.rs"));
+    assert_eq!(transpile(&DAY_TWO), include_str!("transpiled/day_two.rs"));
+    assert_eq!(
+        transpile(&AMPLIFIER),
+        include_str!("transpiled/amplifier.rs")
+    );
+    assert_eq!(transpile(&FEEDBACK), include_str!("transpiled/feedback.rs"));
+    assert_eq!(
+        transpile(&HULL_ROBOT),
+        include_str!("transpiled/hull_robot.rs")
+    );
+    assert_eq!(transpile(&ARCADE), include_str!("transpiled/arcade.rs"));
+}
+
+#[test]
+fn compiled_programs_match_the_interpreter() {
+    let mut program = quine::Program::new();
+    let mut io = QueueProvider::new();
+
+    assert_eq!(program.run(&mut io), Ok(RunState::Halted));
+    assert_eq!(io.drain_outputs(), interpret(&QUINE, &[]));
+    assert!(!program.is_interpreted());
+
+    for input in 0..16 {
+        let mut program = compare::Program::default();
+        let mut io = QueueProvider::with_inputs(vec![input]);
+
+        program.run(&mut io).unwrap();
+        assert_eq!(io.drain_outputs(), interpret(&COMPARE, &[input]));
+        assert!(program.is_halted());
+    }
+}
+
+#[test]
+fn compiled_day_examples_match_the_interpreter() {
+    assert_matches_interpreter(day_two::Program::new(), &DAY_TWO, &[]);
+    assert_matches_interpreter(quine::Program::new(), &QUINE, &[]);
+    for input in [-8, 7, 8, 9] {
+        assert_matches_interpreter(compare::Program::new(), &COMPARE, &[&[input]]);
+    }
+    for phase in 0..5 {
+        let inputs: &[&[i64]] = &[&[phase], &[17]];
+        assert_matches_interpreter(amplifier::Program::new(), &AMPLIFIER, inputs);
+    }
+    for phase in 5..10 {
+        let inputs: &[&[i64]] = &[&[phase, 0], &[1], &[2], &[3], &[4], &[5]];
+        assert_matches_interpreter(feedback::Program::new(), &FEEDBACK, inputs);
+    }
+    for colors in [[0, 0, 0], [1, 0, 1]] {
+        let inputs = colors.map(|color| vec![color]);
+        let inputs: Vec<&[i64]> = inputs.iter().map(Vec::as_slice).collect();
+        assert_matches_interpreter(hull_robot::Program::new(), &HULL_ROBOT, &inputs);
+    }
+    for joystick in -1..=1 {
+        assert_matches_interpreter(arcade::Program::new(), &ARCADE, &[&[joystick]]);
+    }
+}
+
+#[test]
+fn compiled_program_waits_for_input() {
+    let mut program = compare::Program::new();
+    let mut io = QueueProvider::new();
+
+    assert_eq!(program.run(&mut io), Ok(RunState::NeedsInput));
+    assert!(!program.is_halted());
+
+    io.push_input(8);
+    assert_eq!(program.run(&mut io), Ok(RunState::Halted));
+    assert_eq!(program.last_output(), Some(1000));
+    assert_eq!(program.read(21), 8);
+}
+
+#[test]
+fn rewritten_code_falls_back_to_the_interpreter() {
+    let mut program = self_modifying::Program::new();
+    let mut io = QueueProvider::new();
+
+    assert_eq!(program.run(&mut io), Ok(RunState::Halted));
+    assert_eq!(io.drain_outputs(), interpret(&SELF_MODIFYING, &[]));
+    assert!(program.is_interpreted());
+    assert_eq!(program.read(0), 99);
+}
+
+#[test]
+fn compiled_arithmetic_overflow_is_an_error() {
+    let mut program = overflow::Program::new();
+    let mut machine = Machine::with_provider(OVERFLOW.to_vec(), QueueProvider::new());
+
+    let expected = Err(IntcodeError::ArithmeticOverflow {
+        program_counter: 4,
+        instruction: 1102,
+    });
+    assert_eq!(program.run(&mut QueueProvider::new()), expected);
+    assert_eq!(machine.run(), expected);
+    assert_eq!(program.read(9), 3);
+}
+
+#[test]
+fn compiled_puzzle_inputs_match_the_interpreter() {
+    let input_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("input");
+    let dir = std::env::temp_dir().join(format!("intcode-transpile-{}", std::process::id()));
+
+    for &(day, runs) in PUZZLE_RUNS {
+        // Puzzle inputs are not checked in, so only the ones present are compared.
+        let path = input_dir.join(format!("day{:02}.txt", day));
+        let input = match std::fs::read(&path) {
+            Ok(input) => input,
+            Err(_) => continue,
+        };
+        let program = read_intcode_program(&input[..]).unwrap();
+        let binary = compile_puzzle(&program, &dir.join(format!("day{:02}", day)));
+
+        for &inputs in runs {
+            let mut machine = Machine::with_provider(
+                program.clone(),
+                QueueProvider::with_inputs(inputs.to_vec()),
+            );
+            let state = machine.run();
+            let expected = format!("{:?} {:?}\n", state, machine.provider_mut().drain_outputs());
+
+            let output = Command::new(&binary)
+                .args(inputs.iter().map(i64::to_string))
+                .output()
+                .unwrap();
+            assert_eq!(
+                String::from_utf8_lossy(&output.stdout),
+                expected,
+                "day {} with inputs {:?}",
+                day,
+                inputs
+            );
+        }
+    }
+
+    if dir.exists() {
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
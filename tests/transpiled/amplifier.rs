@@ -0,0 +1,166 @@
+// Generated by intcode-transpile; do not edit.
+
+use aoc::intcode::{IntcodeError, IoProvider, Machine, RunState, Snapshot};
+
+const PROGRAM: [i64; 17] = [3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0];
+
+// Start address of the compiled instruction each program word belongs to.
+const OWNER: [usize; 17] = [0, 0, 2, 2, 4, 4, 4, 4, 8, 8, 8, 8, 12, 12, 14, NONE, NONE];
+
+const NONE: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    memory: Vec<i64>,
+    stale: Vec<bool>,
+    pc: usize,
+    rb: i64,
+    halted: bool,
+    last_output: Option<i64>,
+    interpreted: bool,
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Program::new()
+    }
+}
+
+impl Program {
+    pub fn new() -> Self {
+        Program {
+            memory: PROGRAM.to_vec(),
+            stale: vec![false; PROGRAM.len()],
+            pc: 0,
+            rb: 0,
+            halted: false,
+            last_output: None,
+            interpreted: false,
+        }
+    }
+
+    pub fn read(&self, address: usize) -> i64 {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    pub fn last_output(&self) -> Option<i64> {
+        self.last_output
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Whether execution has been handed over to the interpreter.
+    pub fn is_interpreted(&self) -> bool {
+        self.interpreted
+    }
+
+    fn address(&self, value: i64, instruction: i64) -> Result<usize, IntcodeError> {
+        usize::try_from(value).map_err(|_| IntcodeError::NegativeAddress {
+            program_counter: self.pc,
+            instruction,
+            address: value,
+        })
+    }
+
+    fn checked(&self, result: Option<i64>, instruction: i64) -> Result<i64, IntcodeError> {
+        result.ok_or(IntcodeError::ArithmeticOverflow {
+            program_counter: self.pc,
+            instruction,
+        })
+    }
+
+    fn store(&mut self, address: usize, value: i64) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+
+        if let Some(&owner) = OWNER.get(address) {
+            if owner != NONE && self.memory[address] != value {
+                self.stale[owner] = true;
+            }
+        }
+        self.memory[address] = value;
+    }
+
+    fn interpret<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        self.interpreted = true;
+
+        let memory = std::mem::take(&mut self.memory);
+        let mut machine = Machine::from_snapshot(&Snapshot::new(memory, self.pc, self.rb), io);
+        let result = machine.run();
+
+        self.memory = machine.memory().as_slice().to_vec();
+        self.pc = machine.program_counter();
+        self.rb = machine.relative_base();
+        self.halted = machine.is_halted();
+        self.last_output = machine.last_output().or(self.last_output);
+
+        result
+    }
+
+    /// Runs until the program halts or its provider has no input ready.
+    pub fn run<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        if self.halted {
+            return Ok(RunState::Halted);
+        }
+
+        loop {
+            if self.interpreted || self.stale.get(self.pc) == Some(&true) {
+                return self.interpret(io);
+            }
+
+            match self.pc {
+                0 => {
+                    // IN [15]
+                    let d = 15;
+                    match io.try_send_input() {
+                        Some(v) => self.store(d, v),
+                        None => return Ok(RunState::NeedsInput),
+                    }
+                    self.pc = 2;
+                }
+                2 => {
+                    // IN [16]
+                    let d = 16;
+                    match io.try_send_input() {
+                        Some(v) => self.store(d, v),
+                        None => return Ok(RunState::NeedsInput),
+                    }
+                    self.pc = 4;
+                }
+                4 => {
+                    // MUL [16], #10, [16]
+                    let a = self.read(16);
+                    let b = 10;
+                    let d = 16;
+                    self.store(d, self.checked(i64::checked_mul(a, b), 1002)?);
+                    self.pc = 8;
+                }
+                8 => {
+                    // ADD [16], [15], [15]
+                    let a = self.read(16);
+                    let b = self.read(15);
+                    let d = 15;
+                    self.store(d, self.checked(i64::checked_add(a, b), 1)?);
+                    self.pc = 12;
+                }
+                12 => {
+                    // OUT [15]
+                    let a = self.read(15);
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 14;
+                }
+                14 => {
+                    // HLT
+                    self.pc = 15;
+                    self.halted = true;
+                    return Ok(RunState::Halted);
+                }
+                _ => return self.interpret(io),
+            }
+        }
+    }
+}
@@ -0,0 +1,318 @@
+// Generated by intcode-transpile; do not edit.
+
+use aoc::intcode::{IntcodeError, IoProvider, Machine, RunState, Snapshot};
+
+const PROGRAM: [i64; 60] = [1, 100, 100, 100, 104, 0, 104, 0, 104, 1, 104, 1, 104, 0, 104, 2, 104, 2, 104, 0, 104, 1, 104, 1, 104, 2, 104, 3, 104, 2, 104, 1, 104, 4, 104, -1, 104, 0, 104, 0, 3, 59, 104, 1, 104, 0, 104, 0, 1001, 59, 41, 59, 104, -1, 104, 0, 4, 59, 99, 0];
+
+// Start address of the compiled instruction each program word belongs to.
+const OWNER: [usize; 60] = [0, 0, 0, 0, 4, 4, 6, 6, 8, 8, 10, 10, 12, 12, 14, 14, 16, 16, 18, 18, 20, 20, 22, 22, 24, 24, 26, 26, 28, 28, 30, 30, 32, 32, 34, 34, 36, 36, 38, 38, 40, 40, 42, 42, 44, 44, 46, 46, 48, 48, 48, 48, 52, 52, 54, 54, 56, 56, 58, NONE];
+
+const NONE: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    memory: Vec<i64>,
+    stale: Vec<bool>,
+    pc: usize,
+    rb: i64,
+    halted: bool,
+    last_output: Option<i64>,
+    interpreted: bool,
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Program::new()
+    }
+}
+
+impl Program {
+    pub fn new() -> Self {
+        Program {
+            memory: PROGRAM.to_vec(),
+            stale: vec![false; PROGRAM.len()],
+            pc: 0,
+            rb: 0,
+            halted: false,
+            last_output: None,
+            interpreted: false,
+        }
+    }
+
+    pub fn read(&self, address: usize) -> i64 {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    pub fn last_output(&self) -> Option<i64> {
+        self.last_output
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Whether execution has been handed over to the interpreter.
+    pub fn is_interpreted(&self) -> bool {
+        self.interpreted
+    }
+
+    fn address(&self, value: i64, instruction: i64) -> Result<usize, IntcodeError> {
+        usize::try_from(value).map_err(|_| IntcodeError::NegativeAddress {
+            program_counter: self.pc,
+            instruction,
+            address: value,
+        })
+    }
+
+    fn checked(&self, result: Option<i64>, instruction: i64) -> Result<i64, IntcodeError> {
+        result.ok_or(IntcodeError::ArithmeticOverflow {
+            program_counter: self.pc,
+            instruction,
+        })
+    }
+
+    fn store(&mut self, address: usize, value: i64) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+
+        if let Some(&owner) = OWNER.get(address) {
+            if owner != NONE && self.memory[address] != value {
+                self.stale[owner] = true;
+            }
+        }
+        self.memory[address] = value;
+    }
+
+    fn interpret<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        self.interpreted = true;
+
+        let memory = std::mem::take(&mut self.memory);
+        let mut machine = Machine::from_snapshot(&Snapshot::new(memory, self.pc, self.rb), io);
+        let result = machine.run();
+
+        self.memory = machine.memory().as_slice().to_vec();
+        self.pc = machine.program_counter();
+        self.rb = machine.relative_base();
+        self.halted = machine.is_halted();
+        self.last_output = machine.last_output().or(self.last_output);
+
+        result
+    }
+
+    /// Runs until the program halts or its provider has no input ready.
+    pub fn run<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        if self.halted {
+            return Ok(RunState::Halted);
+        }
+
+        loop {
+            if self.interpreted || self.stale.get(self.pc) == Some(&true) {
+                return self.interpret(io);
+            }
+
+            match self.pc {
+                0 => {
+                    // ADD [100], [100], [100]
+                    let a = self.read(100);
+                    let b = self.read(100);
+                    let d = 100;
+                    self.store(d, self.checked(i64::checked_add(a, b), 1)?);
+                    self.pc = 4;
+                }
+                4 => {
+                    // OUT #0
+                    let a = 0;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 6;
+                }
+                6 => {
+                    // OUT #0
+                    let a = 0;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 8;
+                }
+                8 => {
+                    // OUT #1
+                    let a = 1;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 10;
+                }
+                10 => {
+                    // OUT #1
+                    let a = 1;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 12;
+                }
+                12 => {
+                    // OUT #0
+                    let a = 0;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 14;
+                }
+                14 => {
+                    // OUT #2
+                    let a = 2;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 16;
+                }
+                16 => {
+                    // OUT #2
+                    let a = 2;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 18;
+                }
+                18 => {
+                    // OUT #0
+                    let a = 0;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 20;
+                }
+                20 => {
+                    // OUT #1
+                    let a = 1;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 22;
+                }
+                22 => {
+                    // OUT #1
+                    let a = 1;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 24;
+                }
+                24 => {
+                    // OUT #2
+                    let a = 2;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 26;
+                }
+                26 => {
+                    // OUT #3
+                    let a = 3;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 28;
+                }
+                28 => {
+                    // OUT #2
+                    let a = 2;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 30;
+                }
+                30 => {
+                    // OUT #1
+                    let a = 1;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 32;
+                }
+                32 => {
+                    // OUT #4
+                    let a = 4;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 34;
+                }
+                34 => {
+                    // OUT #-1
+                    let a = -1;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 36;
+                }
+                36 => {
+                    // OUT #0
+                    let a = 0;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 38;
+                }
+                38 => {
+                    // OUT #0
+                    let a = 0;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 40;
+                }
+                40 => {
+                    // IN [59]
+                    let d = 59;
+                    match io.try_send_input() {
+                        Some(v) => self.store(d, v),
+                        None => return Ok(RunState::NeedsInput),
+                    }
+                    self.pc = 42;
+                }
+                42 => {
+                    // OUT #1
+                    let a = 1;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 44;
+                }
+                44 => {
+                    // OUT #0
+                    let a = 0;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 46;
+                }
+                46 => {
+                    // OUT #0
+                    let a = 0;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 48;
+                }
+                48 => {
+                    // ADD [59], #41, [59]
+                    let a = self.read(59);
+                    let b = 41;
+                    let d = 59;
+                    self.store(d, self.checked(i64::checked_add(a, b), 1001)?);
+                    self.pc = 52;
+                }
+                52 => {
+                    // OUT #-1
+                    let a = -1;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 54;
+                }
+                54 => {
+                    // OUT #0
+                    let a = 0;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 56;
+                }
+                56 => {
+                    // OUT [59]
+                    let a = self.read(59);
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 58;
+                }
+                58 => {
+                    // HLT
+                    self.pc = 59;
+                    self.halted = true;
+                    return Ok(RunState::Halted);
+                }
+                _ => return self.interpret(io),
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+// Generated by intcode-transpile; do not edit.
+
+use aoc::intcode::{IntcodeError, IoProvider, Machine, RunState, Snapshot};
+
+const PROGRAM: [i64; 47] = [3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20, 1105, 1, 46, 98, 99];
+
+// Start address of the compiled instruction each program word belongs to.
+const OWNER: [usize; 47] = [0, 0, 2, 2, 2, 2, 6, 6, 6, 9, 9, 9, 9, 13, 13, 13, 16, 16, 16, NONE, NONE, NONE, 22, 22, 22, 22, 26, 26, 28, 28, 28, 31, 31, 33, 33, 33, 36, 36, 36, 36, 40, 40, 42, 42, 42, NONE, 46];
+
+const NONE: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    memory: Vec<i64>,
+    stale: Vec<bool>,
+    pc: usize,
+    rb: i64,
+    halted: bool,
+    last_output: Option<i64>,
+    interpreted: bool,
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Program::new()
+    }
+}
+
+impl Program {
+    pub fn new() -> Self {
+        Program {
+            memory: PROGRAM.to_vec(),
+            stale: vec![false; PROGRAM.len()],
+            pc: 0,
+            rb: 0,
+            halted: false,
+            last_output: None,
+            interpreted: false,
+        }
+    }
+
+    pub fn read(&self, address: usize) -> i64 {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    pub fn last_output(&self) -> Option<i64> {
+        self.last_output
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Whether execution has been handed over to the interpreter.
+    pub fn is_interpreted(&self) -> bool {
+        self.interpreted
+    }
+
+    fn address(&self, value: i64, instruction: i64) -> Result<usize, IntcodeError> {
+        usize::try_from(value).map_err(|_| IntcodeError::NegativeAddress {
+            program_counter: self.pc,
+            instruction,
+            address: value,
+        })
+    }
+
+    fn checked(&self, result: Option<i64>, instruction: i64) -> Result<i64, IntcodeError> {
+        result.ok_or(IntcodeError::ArithmeticOverflow {
+            program_counter: self.pc,
+            instruction,
+        })
+    }
+
+    fn store(&mut self, address: usize, value: i64) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+
+        if let Some(&owner) = OWNER.get(address) {
+            if owner != NONE && self.memory[address] != value {
+                self.stale[owner] = true;
+            }
+        }
+        self.memory[address] = value;
+    }
+
+    fn interpret<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        self.interpreted = true;
+
+        let memory = std::mem::take(&mut self.memory);
+        let mut machine = Machine::from_snapshot(&Snapshot::new(memory, self.pc, self.rb), io);
+        let result = machine.run();
+
+        self.memory = machine.memory().as_slice().to_vec();
+        self.pc = machine.program_counter();
+        self.rb = machine.relative_base();
+        self.halted = machine.is_halted();
+        self.last_output = machine.last_output().or(self.last_output);
+
+        result
+    }
+
+    /// Runs until the program halts or its provider has no input ready.
+    pub fn run<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        if self.halted {
+            return Ok(RunState::Halted);
+        }
+
+        loop {
+            if self.interpreted || self.stale.get(self.pc) == Some(&true) {
+                return self.interpret(io);
+            }
+
+            match self.pc {
+                0 => {
+                    // IN [21]
+                    let d = 21;
+                    match io.try_send_input() {
+                        Some(v) => self.store(d, v),
+                        None => return Ok(RunState::NeedsInput),
+                    }
+                    self.pc = 2;
+                }
+                2 => {
+                    // EQ [21], #8, [20]
+                    let a = self.read(21);
+                    let b = 8;
+                    let d = 20;
+                    self.store(d, i64::from(a == b));
+                    self.pc = 6;
+                }
+                6 => {
+                    // JNZ [20], #22
+                    let a = self.read(20);
+                    let b = 22;
+                    if a != 0 {
+                        self.pc = self.address(b, 1005)?;
+                    } else {
+                        self.pc = 9;
+                    }
+                }
+                9 => {
+                    // LT #8, [21], [20]
+                    let a = 8;
+                    let b = self.read(21);
+                    let d = 20;
+                    self.store(d, i64::from(a < b));
+                    self.pc = 13;
+                }
+                13 => {
+                    // JEZ [20], #31
+                    let a = self.read(20);
+                    let b = 31;
+                    if a == 0 {
+                        self.pc = self.address(b, 1006)?;
+                    } else {
+                        self.pc = 16;
+                    }
+                }
+                16 => {
+                    // JEZ #0, #36
+                    let a = 0;
+                    let b = 36;
+                    if a == 0 {
+                        self.pc = self.address(b, 1106)?;
+                    } else {
+                        self.pc = 19;
+                    }
+                }
+                22 => {
+                    // MUL [21], #125, [20]
+                    let a = self.read(21);
+                    let b = 125;
+                    let d = 20;
+                    self.store(d, self.checked(i64::checked_mul(a, b), 1002)?);
+                    self.pc = 26;
+                }
+                26 => {
+                    // OUT [20]
+                    let a = self.read(20);
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 28;
+                }
+                28 => {
+                    // JNZ #1, #46
+                    let a = 1;
+                    let b = 46;
+                    if a != 0 {
+                        self.pc = self.address(b, 1105)?;
+                    } else {
+                        self.pc = 31;
+                    }
+                }
+                31 => {
+                    // OUT #999
+                    let a = 999;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 33;
+                }
+                33 => {
+                    // JNZ #1, #46
+                    let a = 1;
+                    let b = 46;
+                    if a != 0 {
+                        self.pc = self.address(b, 1105)?;
+                    } else {
+                        self.pc = 36;
+                    }
+                }
+                36 => {
+                    // ADD #1000, #1, [20]
+                    let a = 1000;
+                    let b = 1;
+                    let d = 20;
+                    self.store(d, self.checked(i64::checked_add(a, b), 1101)?);
+                    self.pc = 40;
+                }
+                40 => {
+                    // OUT [20]
+                    let a = self.read(20);
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 42;
+                }
+                42 => {
+                    // JNZ #1, #46
+                    let a = 1;
+                    let b = 46;
+                    if a != 0 {
+                        self.pc = self.address(b, 1105)?;
+                    } else {
+                        self.pc = 45;
+                    }
+                }
+                46 => {
+                    // HLT
+                    self.pc = 47;
+                    self.halted = true;
+                    return Ok(RunState::Halted);
+                }
+                _ => return self.interpret(io),
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+// Generated by intcode-transpile; do not edit.
+
+use aoc::intcode::{IntcodeError, IoProvider, Machine, RunState, Snapshot};
+
+const PROGRAM: [i64; 29] = [3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1, 28, 1005, 28, 6, 99, 0, 0, 5];
+
+// Start address of the compiled instruction each program word belongs to.
+const OWNER: [usize; 29] = [0, 0, 2, 2, 2, 2, 6, 6, 8, 8, 8, 8, 12, 12, 12, 12, 16, 16, 18, 18, 18, 18, 22, 22, 22, 25, NONE, NONE, NONE];
+
+const NONE: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    memory: Vec<i64>,
+    stale: Vec<bool>,
+    pc: usize,
+    rb: i64,
+    halted: bool,
+    last_output: Option<i64>,
+    interpreted: bool,
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Program::new()
+    }
+}
+
+impl Program {
+    pub fn new() -> Self {
+        Program {
+            memory: PROGRAM.to_vec(),
+            stale: vec![false; PROGRAM.len()],
+            pc: 0,
+            rb: 0,
+            halted: false,
+            last_output: None,
+            interpreted: false,
+        }
+    }
+
+    pub fn read(&self, address: usize) -> i64 {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    pub fn last_output(&self) -> Option<i64> {
+        self.last_output
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Whether execution has been handed over to the interpreter.
+    pub fn is_interpreted(&self) -> bool {
+        self.interpreted
+    }
+
+    fn address(&self, value: i64, instruction: i64) -> Result<usize, IntcodeError> {
+        usize::try_from(value).map_err(|_| IntcodeError::NegativeAddress {
+            program_counter: self.pc,
+            instruction,
+            address: value,
+        })
+    }
+
+    fn checked(&self, result: Option<i64>, instruction: i64) -> Result<i64, IntcodeError> {
+        result.ok_or(IntcodeError::ArithmeticOverflow {
+            program_counter: self.pc,
+            instruction,
+        })
+    }
+
+    fn store(&mut self, address: usize, value: i64) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+
+        if let Some(&owner) = OWNER.get(address) {
+            if owner != NONE && self.memory[address] != value {
+                self.stale[owner] = true;
+            }
+        }
+        self.memory[address] = value;
+    }
+
+    fn interpret<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        self.interpreted = true;
+
+        let memory = std::mem::take(&mut self.memory);
+        let mut machine = Machine::from_snapshot(&Snapshot::new(memory, self.pc, self.rb), io);
+        let result = machine.run();
+
+        self.memory = machine.memory().as_slice().to_vec();
+        self.pc = machine.program_counter();
+        self.rb = machine.relative_base();
+        self.halted = machine.is_halted();
+        self.last_output = machine.last_output().or(self.last_output);
+
+        result
+    }
+
+    /// Runs until the program halts or its provider has no input ready.
+    pub fn run<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        if self.halted {
+            return Ok(RunState::Halted);
+        }
+
+        loop {
+            if self.interpreted || self.stale.get(self.pc) == Some(&true) {
+                return self.interpret(io);
+            }
+
+            match self.pc {
+                0 => {
+                    // IN [26]
+                    let d = 26;
+                    match io.try_send_input() {
+                        Some(v) => self.store(d, v),
+                        None => return Ok(RunState::NeedsInput),
+                    }
+                    self.pc = 2;
+                }
+                2 => {
+                    // ADD [26], #-4, [26]
+                    let a = self.read(26);
+                    let b = -4;
+                    let d = 26;
+                    self.store(d, self.checked(i64::checked_add(a, b), 1001)?);
+                    self.pc = 6;
+                }
+                6 => {
+                    // IN [27]
+                    let d = 27;
+                    match io.try_send_input() {
+                        Some(v) => self.store(d, v),
+                        None => return Ok(RunState::NeedsInput),
+                    }
+                    self.pc = 8;
+                }
+                8 => {
+                    // MUL [27], #2, [27]
+                    let a = self.read(27);
+                    let b = 2;
+                    let d = 27;
+                    self.store(d, self.checked(i64::checked_mul(a, b), 1002)?);
+                    self.pc = 12;
+                }
+                12 => {
+                    // ADD [27], [26], [27]
+                    let a = self.read(27);
+                    let b = self.read(26);
+                    let d = 27;
+                    self.store(d, self.checked(i64::checked_add(a, b), 1)?);
+                    self.pc = 16;
+                }
+                16 => {
+                    // OUT [27]
+                    let a = self.read(27);
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 18;
+                }
+                18 => {
+                    // ADD [28], #-1, [28]
+                    let a = self.read(28);
+                    let b = -1;
+                    let d = 28;
+                    self.store(d, self.checked(i64::checked_add(a, b), 1001)?);
+                    self.pc = 22;
+                }
+                22 => {
+                    // JNZ [28], #6
+                    let a = self.read(28);
+                    let b = 6;
+                    if a != 0 {
+                        self.pc = self.address(b, 1005)?;
+                    } else {
+                        self.pc = 25;
+                    }
+                }
+                25 => {
+                    // HLT
+                    self.pc = 26;
+                    self.halted = true;
+                    return Ok(RunState::Halted);
+                }
+                _ => return self.interpret(io),
+            }
+        }
+    }
+}
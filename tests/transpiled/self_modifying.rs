@@ -0,0 +1,175 @@
+// Generated by intcode-transpile; do not edit.
+
+use aoc::intcode::{IntcodeError, IoProvider, Machine, RunState, Snapshot};
+
+const PROGRAM: [i64; 20] = [104, 1, 1005, 19, 16, 1101, 0, 99, 0, 1101, 0, 1, 19, 1105, 1, 0, 104, 2, 99, 0];
+
+// Start address of the compiled instruction each program word belongs to.
+const OWNER: [usize; 20] = [0, 0, 2, 2, 2, 5, 5, 5, 5, 9, 9, 9, 9, 13, 13, 13, 16, 16, 18, NONE];
+
+const NONE: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    memory: Vec<i64>,
+    stale: Vec<bool>,
+    pc: usize,
+    rb: i64,
+    halted: bool,
+    last_output: Option<i64>,
+    interpreted: bool,
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Program::new()
+    }
+}
+
+impl Program {
+    pub fn new() -> Self {
+        Program {
+            memory: PROGRAM.to_vec(),
+            stale: vec![false; PROGRAM.len()],
+            pc: 0,
+            rb: 0,
+            halted: false,
+            last_output: None,
+            interpreted: false,
+        }
+    }
+
+    pub fn read(&self, address: usize) -> i64 {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    pub fn last_output(&self) -> Option<i64> {
+        self.last_output
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Whether execution has been handed over to the interpreter.
+    pub fn is_interpreted(&self) -> bool {
+        self.interpreted
+    }
+
+    fn address(&self, value: i64, instruction: i64) -> Result<usize, IntcodeError> {
+        usize::try_from(value).map_err(|_| IntcodeError::NegativeAddress {
+            program_counter: self.pc,
+            instruction,
+            address: value,
+        })
+    }
+
+    fn checked(&self, result: Option<i64>, instruction: i64) -> Result<i64, IntcodeError> {
+        result.ok_or(IntcodeError::ArithmeticOverflow {
+            program_counter: self.pc,
+            instruction,
+        })
+    }
+
+    fn store(&mut self, address: usize, value: i64) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+
+        if let Some(&owner) = OWNER.get(address) {
+            if owner != NONE && self.memory[address] != value {
+                self.stale[owner] = true;
+            }
+        }
+        self.memory[address] = value;
+    }
+
+    fn interpret<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        self.interpreted = true;
+
+        let memory = std::mem::take(&mut self.memory);
+        let mut machine = Machine::from_snapshot(&Snapshot::new(memory, self.pc, self.rb), io);
+        let result = machine.run();
+
+        self.memory = machine.memory().as_slice().to_vec();
+        self.pc = machine.program_counter();
+        self.rb = machine.relative_base();
+        self.halted = machine.is_halted();
+        self.last_output = machine.last_output().or(self.last_output);
+
+        result
+    }
+
+    /// Runs until the program halts or its provider has no input ready.
+    pub fn run<T: IoProvider>(&mut self, io: &mut T) -> Result<RunState, IntcodeError> {
+        if self.halted {
+            return Ok(RunState::Halted);
+        }
+
+        loop {
+            if self.interpreted || self.stale.get(self.pc) == Some(&true) {
+                return self.interpret(io);
+            }
+
+            match self.pc {
+                0 => {
+                    // OUT #1
+                    let a = 1;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 2;
+                }
+                2 => {
+                    // JNZ [19], #16
+                    let a = self.read(19);
+                    let b = 16;
+                    if a != 0 {
+                        self.pc = self.address(b, 1005)?;
+                    } else {
+                        self.pc = 5;
+                    }
+                }
+                5 => {
+                    // ADD #0, #99, [0]
+                    let a = 0;
+                    let b = 99;
+                    let d = 0;
+                    self.store(d, self.checked(i64::checked_add(a, b), 1101)?);
+                    self.pc = 9;
+                }
+                9 => {
+                    // ADD #0, #1, [19]
+                    let a = 0;
+                    let b = 1;
+                    let d = 19;
+                    self.store(d, self.checked(i64::checked_add(a, b), 1101)?);
+                    self.pc = 13;
+                }
+                13 => {
+                    // JNZ #1, #0
+                    let a = 1;
+                    let b = 0;
+                    if a != 0 {
+                        self.pc = self.address(b, 1105)?;
+                    } else {
+                        self.pc = 16;
+                    }
+                }
+                16 => {
+                    // OUT #2
+                    let a = 2;
+                    io.get_output(a);
+                    self.last_output = Some(a);
+                    self.pc = 18;
+                }
+                18 => {
+                    // HLT
+                    self.pc = 19;
+                    self.halted = true;
+                    return Ok(RunState::Halted);
+                }
+                _ => return self.interpret(io),
+            }
+        }
+    }
+}